@@ -1,15 +1,21 @@
-use anyhow::Context;
+use anyhow::Context as _;
 use ratatui::{
-    crossterm::event::{poll, read, Event, KeyCode, KeyEventKind},
+    crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind},
     prelude::*,
-    widgets::Widget,
+    widgets::{Block, Widget},
     Terminal,
 };
 use std::{cell::RefCell, rc::Rc, time::Duration};
 
 use crate::{
-    views::{ListView, NewTaskView, View},
-    widgets::{NotificationStack, TodoItem, TodoList},
+    events::{AppEvent, EventSource},
+    history::History,
+    keymap::{Action, Context, Keymap},
+    reminders::Reminders,
+    storage::{validate_list_name, Storage, DEFAULT_LIST},
+    theme::{Role, Theme},
+    views::{CalendarView, ListView, NewTaskView, NotificationLogView, View, ViewKind},
+    widgets::{HelpPopup, Notification, NotificationLevel, NotificationStack, TodoItem, TodoList},
 };
 
 #[derive(Default, PartialEq)]
@@ -19,11 +25,39 @@ pub enum ApplicationRunningState {
     Exiting,
 }
 
+// the global bindings are only active in the normal mode
 #[derive(Default, PartialEq)]
 pub enum ApplicationMode {
     #[default]
     Normal,
     Writing,
+    // a range of rows is being selected
+    Visual,
+    // a `:` command is being entered
+    Command,
+}
+
+// the items of a list before a change, a move to another list also
+// keeps the items of that list so one undo restores both lists
+#[derive(Default)]
+pub struct Snapshot {
+    pub items: Vec<TodoItem>,
+    pub other: Option<(String, Vec<TodoItem>)>,
+}
+
+impl Snapshot {
+    pub fn new(items: Vec<TodoItem>) -> Self {
+        Self { items, other: None }
+    }
+}
+
+// a list that is not the active one, it keeps its own undo history
+// so switching between lists doesn't lose it
+#[derive(Default)]
+pub struct NamedList {
+    pub name: String,
+    pub todo_list: TodoList,
+    pub history: History<Snapshot>,
 }
 
 #[derive(Default)]
@@ -31,65 +65,393 @@ pub struct ApplicationState {
     pub mode: ApplicationMode,
     pub running_state: ApplicationRunningState,
     pub notifications: NotificationStack,
+    // name of the active list
+    pub list_name: String,
+    // the active list and its undo history
     pub todo_list: TodoList,
+    pub history: History<Snapshot>,
+    // the lists that are not active
+    pub lists: Vec<NamedList>,
+    pub requested_view: Option<ViewKind>,
+    // the reminders shown for the due and overdue tasks
+    pub reminders: Reminders,
 }
 
-#[derive(Default)]
+impl ApplicationState {
+    // applies a change to the todo list, the previous items are
+    // recorded in the history only if the change modified them
+    pub fn edit_list<R>(&mut self, change: impl FnOnce(&mut TodoList) -> R) -> R {
+        let before = self.todo_list.items().to_vec();
+        let result = change(&mut self.todo_list);
+
+        if self.todo_list.items() != before.as_slice() {
+            self.history.record(Snapshot::new(before));
+        }
+        result
+    }
+
+    // pushes the reminders of the due and overdue tasks of every list,
+    // returns true if any reminder was pushed
+    pub fn check_reminders(&mut self) -> bool {
+        if !self.reminders.should_check() {
+            return false;
+        }
+        let active = (self.list_name.as_str(), self.todo_list.items(), true);
+        let others = self
+            .lists
+            .iter()
+            .map(|list| (list.name.as_str(), list.todo_list.items(), false));
+        let notifications = self.reminders.check(std::iter::once(active).chain(others));
+
+        let reminded = !notifications.is_empty();
+        for notification in notifications {
+            self.notifications.push_notification(notification);
+        }
+        reminded
+    }
+
+    // restores the todo list to before the last change,
+    // returns false if there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        let Some(next) = self.history.next_undo() else {
+            return false;
+        };
+        let current = self.snapshot_like(next);
+        match self.history.undo(current) {
+            Some(snapshot) => {
+                self.restore(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // reapplies the last undone change,
+    // returns false if there is nothing to redo
+    pub fn redo(&mut self) -> bool {
+        let Some(next) = self.history.next_redo() else {
+            return false;
+        };
+        let current = self.snapshot_like(next);
+        match self.history.redo(current) {
+            Some(snapshot) => {
+                self.restore(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // the current items of the lists the snapshot restores,
+    // kept so restoring it can be reverted
+    fn snapshot_like(&self, snapshot: &Snapshot) -> Snapshot {
+        let other = snapshot.other.as_ref().and_then(|(name, _)| {
+            let list = self.lists.iter().find(|list| &list.name == name)?;
+            Some((name.clone(), list.todo_list.items().to_vec()))
+        });
+        Snapshot {
+            items: self.todo_list.items().to_vec(),
+            other,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.todo_list.set_items(snapshot.items);
+        if let Some((name, items)) = snapshot.other {
+            if let Some(list) = self.lists.iter_mut().find(|list| list.name == name) {
+                list.todo_list.set_items(items);
+            }
+        }
+    }
+
+    // the names and item counts of all the lists sorted by name
+    pub fn list_counts(&self) -> Vec<(String, usize)> {
+        let mut counts = vec![(self.list_name.clone(), self.todo_list.items().len())];
+        counts.extend(
+            self.lists
+                .iter()
+                .map(|list| (list.name.clone(), list.todo_list.items().len())),
+        );
+        counts.sort();
+        counts
+    }
+
+    // every list with its name, the active list first
+    pub fn all_lists_mut(&mut self) -> Vec<(&str, &mut TodoList)> {
+        let mut lists = vec![(self.list_name.as_str(), &mut self.todo_list)];
+        lists.extend(
+            self.lists
+                .iter_mut()
+                .map(|list| (list.name.as_str(), &mut list.todo_list)),
+        );
+        lists
+    }
+
+    // adds a new empty list, it is written to disk
+    // right away so it exists even while empty
+    pub fn create_list(&mut self, name: &str) -> Result<(), String> {
+        validate_list_name(name)?;
+        if self.list_counts().iter().any(|(n, _)| n == name) {
+            return Err(format!("a list named `{}` already exists", name));
+        }
+
+        let mut todo_list = TodoList::default();
+        todo_list.mark_changed();
+        self.lists.push(NamedList {
+            name: name.to_string(),
+            todo_list,
+            history: History::default(),
+        });
+        Ok(())
+    }
+
+    // makes the list with the given name the active list,
+    // returns false if there is no such list
+    pub fn switch_list(&mut self, name: &str) -> bool {
+        if self.list_name == name {
+            return true;
+        }
+        let Some(i) = self.lists.iter().position(|list| list.name == name) else {
+            return false;
+        };
+
+        let next = self.lists.remove(i);
+        let previous = NamedList {
+            name: std::mem::replace(&mut self.list_name, next.name),
+            todo_list: std::mem::replace(&mut self.todo_list, next.todo_list),
+            history: std::mem::replace(&mut self.history, next.history),
+        };
+        self.lists.push(previous);
+        true
+    }
+
+    // moves the items with their subtasks to the list with the given
+    // name, a single undo of the active list restores both lists,
+    // returns the moved item titles or a message describing the failure
+    pub fn move_items_to(&mut self, name: &str, ids: &[u64]) -> Result<Vec<String>, String> {
+        if name == self.list_name {
+            return Err(format!("tasks are already in `{}`", name));
+        }
+        let Some(target) = self.lists.iter().position(|list| list.name == name) else {
+            return Err(format!("no list named `{}` to move the task to", name));
+        };
+        if ids.is_empty() {
+            return Err("select a task to move".into());
+        }
+
+        let before = self.todo_list.items().to_vec();
+        let trees = self.todo_list.remove_trees(ids);
+        if trees.is_empty() {
+            return Err("the tasks to move are not in the list anymore".into());
+        }
+        let target = &mut self.lists[target];
+        self.history.record(Snapshot {
+            items: before,
+            other: Some((target.name.clone(), target.todo_list.items().to_vec())),
+        });
+
+        let mut titles = Vec::new();
+        for items in trees {
+            titles.push(items[0].title().clone());
+            target.todo_list.add_tree(items);
+        }
+        Ok(titles)
+    }
+}
+
+// how often tick events are produced while idle
+const TICK_RATE: Duration = Duration::from_millis(250);
+
 pub struct Application {
     state: Rc<RefCell<ApplicationState>>,
     current_view: Option<Box<dyn View>>,
+    // shown over the current view while open
+    help: Option<HelpPopup>,
+    storage: Storage,
+    // set when the screen doesn't show the current state anymore
+    needs_redraw: bool,
 }
 
 impl Application {
-    pub fn init(&mut self) {
-        self.load_data();
-        self.current_view = Some(Box::new(ListView::new(Rc::clone(&self.state))));
+    pub fn new(storage: Storage) -> Self {
+        Self {
+            state: Rc::default(),
+            current_view: None,
+            help: None,
+            storage,
+            needs_redraw: true,
+        }
     }
 
-    pub fn load_data(&self) {
+    pub fn init(&mut self) -> anyhow::Result<()> {
+        self.load_data()?;
+        self.open_view(ViewKind::List);
+        self.report_keymap_conflicts();
+        Ok(())
+    }
+
+    fn report_keymap_conflicts(&self) {
         let mut state = self.state.borrow_mut();
-        state.todo_list.add(TodoItem::new(
-            "hello world".into(),
-            "simple todo task example".into(),
-        ));
-        state
-            .todo_list
-            .add(TodoItem::new("sleep".into(), "sleep or whatever".into()));
-        state.todo_list.add(TodoItem::new(
-            "buy that X item".into(),
-            "Go to the mall and buy X".into(),
-        ));
-        state
-            .todo_list
-            .add(TodoItem::new("test".into(), "test the item".into()));
-        state.todo_list.add(TodoItem::new(
-            "call X".into(),
-            "number: 123-456-7890".into(),
-        ));
+        for conflict in Keymap::current().conflicts() {
+            state.notifications.push_notification(Notification::new(
+                " key binding problem ".into(),
+                conflict.clone(),
+                Duration::from_secs(10),
+                NotificationLevel::Warn,
+            ));
+        }
     }
 
+    // replaces the current view, the mode is reset since
+    // the new view starts without any focused input
+    pub fn open_view(&mut self, kind: ViewKind) {
+        self.state.borrow_mut().mode = ApplicationMode::Normal;
+
+        let state = Rc::clone(&self.state);
+        self.current_view = Some(match kind {
+            ViewKind::List => Box::new(ListView::new(state)),
+            ViewKind::NewTask => Box::new(NewTaskView::new(state)),
+            ViewKind::NewSubtask(parent) => Box::new(NewTaskView::subtask(state, parent)),
+            ViewKind::EditTask(id) => Box::new(NewTaskView::edit(state, id)),
+            ViewKind::Notifications => Box::new(NotificationLogView::new(state)),
+            ViewKind::Calendar => Box::new(CalendarView::new(state)),
+        });
+    }
+
+    // loads all the stored lists, the default list is the active one
+    pub fn load_data(&self) -> anyhow::Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.list_name = DEFAULT_LIST.to_string();
+        state.todo_list = self.storage.load(DEFAULT_LIST)?;
+
+        for name in self.storage.list_names()? {
+            if name != DEFAULT_LIST {
+                let todo_list = self.storage.load(&name)?;
+                state.lists.push(NamedList {
+                    name,
+                    todo_list,
+                    history: History::default(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn save_data(&self) -> anyhow::Result<()> {
+        for (name, todo_list) in self.state.borrow_mut().all_lists_mut() {
+            self.storage.save(name, todo_list)?;
+        }
+        Ok(())
+    }
+
+    // waits for events and handles them until the user quits, the
+    // screen is drawn only after an event that changed what is shown
     pub fn run(&mut self, mut terminal: Terminal<impl Backend>) -> anyhow::Result<()> {
+        let mut events = EventSource::new(TICK_RATE)?;
         while self.state.borrow().running_state != ApplicationRunningState::Exiting {
-            terminal
-                .draw(|f| f.render_widget(&mut *self, f.size()))
-                .context("couldn't draw new frame to terminal screen")?;
-            self.wait_for_key_event()?;
+            if std::mem::take(&mut self.needs_redraw) {
+                terminal
+                    .draw(|f| f.render_widget(&mut *self, f.size()))
+                    .context("couldn't draw new frame to terminal screen")?;
+            }
+
+            match events.next()? {
+                AppEvent::Suspend => {
+                    // save first, the process may never be resumed
+                    self.save_if_changed();
+                    crate::tui::suspend()?;
+                    terminal.clear()?;
+                    self.needs_redraw = true;
+                }
+                event => self.handle_event(event),
+            }
+            self.save_if_changed();
         }
-        Ok(())
+        self.save_data()
     }
 
-    fn wait_for_key_event(&mut self) -> anyhow::Result<()> {
-        if poll(Duration::from_secs(0))? {
-            if let Event::Key(key_event) = read().context("couldn't read input key event")? {
-                if key_event.kind == KeyEventKind::Press {
-                    self.handle_key_event(key_event.code);
+    fn handle_event(&mut self, event: AppEvent) {
+        match event {
+            AppEvent::Key(key) => self.handle_key_event(key),
+            AppEvent::Mouse(mouse) => self.handle_mouse_event(mouse),
+            AppEvent::Paste(text) => {
+                if let Some(v) = &mut self.current_view {
+                    v.view_event_paste(&text);
                 }
             }
+            AppEvent::Tick => {
+                // nothing changed on screen unless a notification
+                // expired or a reminder was shown
+                let mut state = self.state.borrow_mut();
+                let expired = state.notifications.remove_expired();
+                let reminded = state.check_reminders();
+                self.needs_redraw |= expired || reminded;
+                return;
+            }
+            // the terminal may have been drawn over while unfocused
+            AppEvent::Resize | AppEvent::FocusGained => {}
+            AppEvent::FocusLost => return,
+            AppEvent::Terminate => {
+                self.state.borrow_mut().running_state = ApplicationRunningState::Exiting;
+                return;
+            }
+            AppEvent::Suspend => return,
+        }
+        self.needs_redraw = true;
+    }
+
+    // writes the changed lists to disk, failing to save while running
+    // is not fatal, so the user is notified instead and the lists
+    // will be saved again on the next change or on exit
+    fn save_if_changed(&mut self) {
+        let mut state = self.state.borrow_mut();
+        let mut errors = Vec::new();
+        for (name, todo_list) in state.all_lists_mut() {
+            if todo_list.take_changed() {
+                if let Err(e) = self.storage.save(name, todo_list) {
+                    errors.push(e);
+                }
+            }
+        }
+
+        for e in errors {
+            state.notifications.push_notification(Notification::new(
+                " save failed ".into(),
+                format!("{:#}", e),
+                Duration::from_secs(5),
+                NotificationLevel::Error,
+            ));
         }
-        Ok(())
     }
 
-    fn handle_key_event(&mut self, key: KeyCode) {
+    // notifications are drawn on top of the view so a
+    // click on a notification dismisses it instead of reaching the view
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) {
+        if let Some(help) = &mut self.help {
+            match mouse.kind {
+                MouseEventKind::ScrollDown => help.scroll_down(),
+                MouseEventKind::ScrollUp => help.scroll_up(),
+                _ => {}
+            }
+            return;
+        }
+        if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+            let position = Position::new(mouse.column, mouse.row);
+            if self.state.borrow_mut().notifications.dismiss_at(position) {
+                return;
+            }
+        }
+
+        if let Some(v) = &mut self.current_view {
+            v.view_event_mouse(mouse);
+        }
+        let requested_view = self.state.borrow_mut().requested_view.take();
+        if let Some(kind) = requested_view {
+            self.open_view(kind);
+        }
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
         // lathough it would be logical to first
         // match the `self.state.mode` and based on the mode go to different
         // handlers like so
@@ -110,25 +472,63 @@ impl Application {
         // `running_state`, but we can't `borrow_mut` because our `self.state.borrow` is still
         // alive and will crash the program, thats why I have this ugly ass solution with if
         // matches
-        match key {
-            KeyCode::Char('n') if self.state.borrow().mode == ApplicationMode::Normal => {
-                self.current_view = Some(Box::new(NewTaskView::new(Rc::clone(&self.state))));
+        if let Some(help) = &mut self.help {
+            // the help popup takes every key while it is open
+            match Keymap::current().action(Context::Help, &key) {
+                Some(Action::Up) => help.scroll_up(),
+                Some(Action::Down) => help.scroll_down(),
+                Some(Action::Cancel) => self.help = None,
+                _ => {}
+            }
+            return;
+        }
+
+        let action = Keymap::current().action(Context::Global, &key);
+        match action {
+            Some(Action::NewTask) if self.state.borrow().mode == ApplicationMode::Normal => {
+                self.open_view(ViewKind::NewTask);
+            }
+            Some(Action::ListTasks) if self.state.borrow().mode == ApplicationMode::Normal => {
+                self.open_view(ViewKind::List);
             }
-            KeyCode::Char('l') if self.state.borrow().mode == ApplicationMode::Normal => {
-                self.current_view = Some(Box::new(ListView::new(Rc::clone(&self.state))));
+            Some(Action::Calendar) if self.state.borrow().mode == ApplicationMode::Normal => {
+                self.open_view(ViewKind::Calendar);
             }
-            KeyCode::Char('q') | KeyCode::Esc
+            Some(Action::Notifications) if self.state.borrow().mode == ApplicationMode::Normal => {
+                self.open_view(ViewKind::Notifications);
+            }
+            Some(Action::DismissNotification)
                 if self.state.borrow().mode == ApplicationMode::Normal =>
             {
+                self.state.borrow_mut().notifications.dismiss_top();
+            }
+            Some(Action::ClearNotifications)
+                if self.state.borrow().mode == ApplicationMode::Normal =>
+            {
+                self.state.borrow_mut().notifications.clear();
+            }
+            Some(Action::PinNotification)
+                if self.state.borrow().mode == ApplicationMode::Normal =>
+            {
+                self.state.borrow_mut().notifications.toggle_pin_top();
+            }
+            Some(Action::Help) if self.state.borrow().mode == ApplicationMode::Normal => {
+                self.help = Some(HelpPopup::default());
+            }
+            Some(Action::Quit) if self.state.borrow().mode == ApplicationMode::Normal => {
                 self.state.borrow_mut().running_state = ApplicationRunningState::Exiting
             }
-            _ => match &mut self.current_view {
-                Some(v) => {
+            _ => {
+                if let Some(v) = &mut self.current_view {
                     v.view_event_key(key);
                 }
-                _ => {}
-            },
+            }
         };
+
+        let requested_view = self.state.borrow_mut().requested_view.take();
+        if let Some(kind) = requested_view {
+            self.open_view(kind);
+        }
     }
 }
 
@@ -137,13 +537,59 @@ impl Widget for &mut Application {
     where
         Self: Sized,
     {
+        Block::default()
+            .style(Theme::current().style(Role::Base))
+            .render(area, buf);
         match &mut self.current_view {
             Some(v) => v.render_view(area, buf),
             None => panic!("application `run` is called before setting the `current_view`"),
         }
+        if let Some(help) = &mut self.help {
+            help.render(area, buf);
+        }
 
         if !self.state.borrow().notifications.is_empty() {
             self.state.borrow_mut().notifications.render(area, buf);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titles(list: &TodoList) -> Vec<&str> {
+        list.items()
+            .iter()
+            .map(|item| item.title().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn undoing_a_move_restores_both_lists() {
+        let mut state = ApplicationState {
+            list_name: "work".into(),
+            lists: vec![NamedList {
+                name: "home".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        state
+            .todo_list
+            .add(TodoItem::new("report".into(), String::new()));
+        let id = state.todo_list.items()[0].id();
+
+        state.move_items_to("home", &[id]).unwrap();
+        assert!(state.todo_list.items().is_empty());
+        assert_eq!(titles(&state.lists[0].todo_list), ["report"]);
+
+        assert!(state.undo());
+        assert_eq!(titles(&state.todo_list), ["report"]);
+        assert!(state.lists[0].todo_list.items().is_empty());
+
+        assert!(state.redo());
+        assert!(state.todo_list.items().is_empty());
+        assert_eq!(titles(&state.lists[0].todo_list), ["report"]);
+    }
+}
@@ -0,0 +1,194 @@
+use anyhow::Context;
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use std::{
+    io::{Read, Write},
+    path::PathBuf,
+};
+
+use crate::{
+    formats::{self, Format},
+    storage::{validate_list_name, Storage, DEFAULT_LIST},
+    widgets::{parse_tags, Priority, Repeat, TodoItem, TodoItemStatus},
+    workflow::Workflow,
+};
+
+#[derive(Parser)]
+#[command(
+    version,
+    about = "a todo application, run without a command to open the TUI"
+)]
+pub struct Cli {
+    /// The list the command works on
+    #[arg(short = 'L', long, global = true, default_value = DEFAULT_LIST)]
+    pub list: String,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Add a new task and print its id
+    Add {
+        title: String,
+        #[arg(short, long, default_value = "")]
+        description: String,
+        /// Due date as YYYY-MM-DD
+        #[arg(long)]
+        due: Option<NaiveDate>,
+        /// One of none, low, medium or high
+        #[arg(short, long, default_value = "none")]
+        priority: Priority,
+        /// Comma separated tags, can be given multiple times
+        #[arg(short, long)]
+        tag: Vec<String>,
+        /// Id of the task to add the new task as a subtask of
+        #[arg(long)]
+        parent: Option<u64>,
+        /// One of daily, "weekly [mon,thu]", "monthly [15]" or "after N days"
+        #[arg(short, long)]
+        repeat: Option<Repeat>,
+    },
+    /// List the tasks as tab separated `id status title` lines
+    List,
+    /// Mark a task as done
+    Done { id: u64 },
+    /// Set the status of a task to one of the configured statuses
+    Status { id: u64, status: String },
+    /// Remove a task and its subtasks
+    Rm { id: u64 },
+    /// List the lists as tab separated `name count` lines
+    Lists,
+    /// Move a task and its subtasks to another list and print its new id
+    Mv { id: u64, to: String },
+    /// Write the tasks to a file, or to stdout when no file is given
+    Export {
+        file: Option<PathBuf>,
+        /// One of todotxt, markdown, json or csv, taken from the file extension when not given
+        #[arg(short, long)]
+        format: Option<Format>,
+    },
+    /// Add the tasks read from a file, `-` reads stdin, and print how many were added
+    Import {
+        file: PathBuf,
+        /// One of todotxt, markdown, json or csv, taken from the file extension when not given
+        #[arg(short, long)]
+        format: Option<Format>,
+    },
+}
+
+// runs the command headlessly against the stored list with the
+// given name, the list is saved back to disk only if the command changed it
+pub fn run(list: &str, command: Command, storage: &Storage) -> anyhow::Result<()> {
+    validate_list_name(list).map_err(anyhow::Error::msg)?;
+    let mut todo_list = storage.load(list)?;
+
+    match command {
+        Command::Add {
+            title,
+            description,
+            due,
+            priority,
+            tag,
+            parent,
+            repeat,
+        } => {
+            // the same checks as the new task form
+            let title = title.trim();
+            if title.is_empty() {
+                anyhow::bail!("a task must have a title");
+            }
+            let mut item = TodoItem::new(title.to_string(), description.trim().to_string());
+            item.set_due(due);
+            item.set_priority(priority);
+            item.set_tags(parse_tags(&tag.join(",")));
+            item.set_repeat(repeat);
+
+            let id = match parent {
+                Some(parent) => todo_list
+                    .add_child(parent, item)
+                    .with_context(|| format!("no task with id {}", parent))?,
+                None => todo_list.add(item),
+            };
+            println!("{}", id);
+        }
+        Command::List => {
+            for item in todo_list.items() {
+                println!("{}\t{}\t{}", item.id(), item.status(), item.title());
+            }
+        }
+        Command::Done { id } => {
+            if !todo_list.set_status_of(id, TodoItemStatus::done()) {
+                anyhow::bail!("no task with id {}", id);
+            }
+        }
+        Command::Status { id, status } => {
+            let workflow = Workflow::current();
+            if workflow.get(&status).is_none() {
+                let names: Vec<&str> = workflow
+                    .statuses()
+                    .iter()
+                    .map(|s| s.name.as_str())
+                    .collect();
+                anyhow::bail!(
+                    "unknown status `{}`, expected one of: {}",
+                    status,
+                    names.join(", ")
+                );
+            }
+
+            if !todo_list.set_status_of(id, TodoItemStatus::from(status.as_str())) {
+                anyhow::bail!("no task with id {}", id);
+            }
+        }
+        Command::Rm { id } => {
+            todo_list
+                .remove(id)
+                .with_context(|| format!("no task with id {}", id))?;
+        }
+        Command::Lists => {
+            for name in storage.list_names()? {
+                println!("{}\t{}", name, storage.load(&name)?.items().len());
+            }
+        }
+        Command::Mv { id, to } => {
+            validate_list_name(&to).map_err(anyhow::Error::msg)?;
+            if to == list {
+                anyhow::bail!("task {} is already in `{}`", id, to);
+            }
+
+            let items = todo_list.remove_tree(id);
+            if items.is_empty() {
+                anyhow::bail!("no task with id {}", id);
+            }
+            let mut target = storage.load(&to)?;
+            let new_id = target.add_tree(items).unwrap_or_default();
+            // the target is saved first so a failure never loses the task
+            storage.save(&to, &target)?;
+            println!("{}", new_id);
+        }
+        Command::Export { file, format } => match file {
+            Some(file) => formats::export_file(&file, format, todo_list.items())?,
+            None => {
+                let content = formats::export(Format::resolve(format, None)?, todo_list.items())?;
+                std::io::stdout().write_all(content.as_bytes())?;
+            }
+        },
+        Command::Import { file, format } => {
+            let items = if file.as_os_str() == "-" {
+                let mut content = String::new();
+                std::io::stdin().read_to_string(&mut content)?;
+                formats::import(Format::resolve(format, None)?, &content)
+                    .context("couldn't import stdin")?
+            } else {
+                formats::import_file(&file, format)?
+            };
+            println!("{}", todo_list.add_items(items).len());
+        }
+    }
+
+    if todo_list.take_changed() {
+        storage.save(list, &todo_list)?;
+    }
+    Ok(())
+}
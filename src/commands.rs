@@ -0,0 +1,154 @@
+use std::{path::PathBuf, str::FromStr};
+
+use crate::{
+    app::ApplicationState,
+    widgets::{parse_tags, GroupBy, SortKey},
+    workflow::Workflow,
+};
+
+// the command names offered when completing the first word
+const COMMAND_NAMES: [&str; 10] = [
+    "export", "filter", "group", "import", "list", "quit", "sort", "tag", "wq", "write",
+];
+
+// a command entered in the `:` command line of the list view
+pub enum Command {
+    // saves every list, lists are saved after every change anyway
+    Write,
+    Quit,
+    WriteQuit,
+    Sort(SortKey),
+    Group(GroupBy),
+    // replaces the filter of the list, an empty filter shows everything
+    Filter(String),
+    // opens the list with the name
+    List(String),
+    // selects the row with the number, counted from 1
+    Row(usize),
+    // adds tags to the marked or selected items, and removes
+    // the tags written with a leading `-`
+    Tag {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    // writes the active list to the file, in the format of its extension
+    Export(PathBuf),
+    // adds the tasks of the file to the active list
+    Import(PathBuf),
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (name, args) = s.split_once(' ').unwrap_or((s, ""));
+        let args = args.trim();
+        let no_args = |command| {
+            if args.is_empty() {
+                Ok(command)
+            } else {
+                Err(format!("`{}` takes no arguments", name))
+            }
+        };
+
+        match name {
+            "w" | "write" => no_args(Command::Write),
+            "q" | "quit" => no_args(Command::Quit),
+            "wq" | "x" => no_args(Command::WriteQuit),
+            "sort" => Ok(Command::Sort(args.parse().map_err(|e| {
+                format!("{}, expected one of {}", e, sort_names().join(", "))
+            })?)),
+            "group" => Ok(Command::Group(args.parse().map_err(|e| {
+                format!("{}, expected one of {}", e, group_names().join(", "))
+            })?)),
+            "filter" => Ok(Command::Filter(args.to_string())),
+            "list" if args.is_empty() => Err("`list` needs the name of a list".into()),
+            "list" => Ok(Command::List(args.to_string())),
+            "tag" => {
+                let (remove, add): (Vec<&str>, Vec<&str>) = args
+                    .split_whitespace()
+                    .partition(|tag| tag.starts_with('-'));
+                let add = parse_tags(&add.join(" "));
+                let remove: Vec<&str> = remove.iter().map(|t| &t[1..]).collect();
+                let remove = parse_tags(&remove.join(" "));
+                if add.is_empty() && remove.is_empty() {
+                    return Err("`tag` needs tags to add, or to remove when starting with -".into());
+                }
+                Ok(Command::Tag { add, remove })
+            }
+            "export" | "import" if args.is_empty() => Err(format!("`{}` needs a file", name)),
+            "export" => Ok(Command::Export(expand_home(args))),
+            "import" => Ok(Command::Import(expand_home(args))),
+            _ => match name.parse::<usize>() {
+                Ok(row) if args.is_empty() => Ok(Command::Row(row)),
+                _ => Err(format!("not a command: `{}`", s)),
+            },
+        }
+    }
+}
+
+// the candidates for the last word of the command line and the byte index
+// where that word starts, the first word is completed to a command name and
+// the following words to the arguments of the command
+pub fn complete(input: &str, state: &ApplicationState) -> (usize, Vec<String>) {
+    let start = input.rfind(' ').map_or(0, |i| i + 1);
+    let word = &input[start..];
+
+    let candidates = if start == 0 {
+        COMMAND_NAMES.iter().map(|name| name.to_string()).collect()
+    } else {
+        match input.split_whitespace().next().unwrap_or_default() {
+            "sort" => sort_names(),
+            "group" => group_names(),
+            "list" => state
+                .list_counts()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect(),
+            "filter" => filter_terms(state),
+            "tag" => {
+                let tags = state.todo_list.tags();
+                let removals: Vec<String> = tags.iter().map(|t| format!("-{}", t)).collect();
+                tags.into_iter().chain(removals).collect()
+            }
+            _ => Vec::new(),
+        }
+    };
+    let candidates = candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(word))
+        .collect();
+    (start, candidates)
+}
+
+// paths starting with `~/` are relative to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn sort_names() -> Vec<String> {
+    SortKey::ALL.iter().map(|k| k.name().to_string()).collect()
+}
+
+fn group_names() -> Vec<String> {
+    GroupBy::ALL.iter().map(|g| g.name().to_string()).collect()
+}
+
+// `tag:` terms for the tags of the active list and `status:` terms
+// for the workflow statuses
+fn filter_terms(state: &ApplicationState) -> Vec<String> {
+    let tags = state
+        .todo_list
+        .tags()
+        .into_iter()
+        .map(|t| format!("tag:{}", t));
+    let statuses = Workflow::current()
+        .statuses()
+        .iter()
+        .map(|s| format!("status:{}", s.name.replace(' ', "-")));
+    tags.chain(statuses).collect()
+}
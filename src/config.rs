@@ -0,0 +1,79 @@
+use anyhow::Context;
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::{
+    keymap::Keymap, reminders::ReminderConfig, theme::Theme, widgets::NotificationConfig,
+    workflow::Workflow,
+};
+
+// environment variable that overrides the config file path
+const CONFIG_PATH_ENV: &str = "TOODU_CONFIG";
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+// the directory of the loaded config file, paths in the config are relative to it
+static CONFIG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+// user configuration loaded from `config.toml`, every
+// field is optional and falls back to the defaults
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    #[serde(rename = "statuses")]
+    pub workflow: Workflow,
+    #[serde(rename = "keys")]
+    pub keymap: Keymap,
+    pub theme: Theme,
+    pub reminders: ReminderConfig,
+    pub notifications: NotificationConfig,
+}
+
+impl Config {
+    // loads the config from `$TOODU_CONFIG` if the variable is set, otherwise
+    // from the platform config directory (`~/.config/toodu/config.toml` on linux),
+    // a missing config file results in the default config
+    pub fn load() -> anyhow::Result<Self> {
+        let path = match std::env::var_os(CONFIG_PATH_ENV) {
+            Some(path) => PathBuf::from(path),
+            None => match dirs::config_dir() {
+                Some(dir) => dir.join("toodu").join(CONFIG_FILE_NAME),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        if let Some(dir) = path.parent() {
+            let _ = CONFIG_DIR.set(dir.to_path_buf());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("couldn't read config file `{}`", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("couldn't parse config file `{}`", path.display()))
+    }
+}
+
+// sets the config used by the application, should be
+// called once at startup before anything reads the config
+pub fn init(config: Config) {
+    // the config can only be set once, later calls are ignored
+    let _ = CONFIG.set(config);
+}
+
+// returns the application config, the default config
+// is used if `init` was never called
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+// the directory of the config file, `None` when no config file was loaded
+pub fn dir() -> Option<&'static Path> {
+    CONFIG_DIR.get().map(PathBuf::as_path)
+}
@@ -0,0 +1,101 @@
+use anyhow::Context;
+use ratatui::crossterm::event::{
+    poll, read, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind,
+};
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+// the events the application reacts to
+pub enum AppEvent {
+    Key(KeyEvent),
+    // clicks and scroll wheel events, mouse moves are left out
+    Mouse(MouseEvent),
+    Paste(String),
+    Resize,
+    FocusGained,
+    FocusLost,
+    // sent every tick rate while no other event arrives, used
+    // for anything that changes with time like notification expiry
+    Tick,
+    // Ctrl-Z, the application should suspend itself
+    Suspend,
+    // Ctrl-C, SIGINT, SIGTERM or SIGHUP, the application
+    // should save and exit
+    Terminate,
+}
+
+// waits for terminal events, producing a tick whenever
+// the tick rate passes without an event
+pub struct EventSource {
+    tick_rate: Duration,
+    last_tick: Instant,
+    // set by the signal handlers when a termination signal arrives
+    terminate: Arc<AtomicBool>,
+}
+
+impl EventSource {
+    // the termination signals are caught from now on instead
+    // of killing the process, they are checked on every tick
+    pub fn new(tick_rate: Duration) -> anyhow::Result<Self> {
+        let terminate = Arc::new(AtomicBool::new(false));
+        for signal in [SIGTERM, SIGHUP, SIGINT] {
+            signal_hook::flag::register(signal, Arc::clone(&terminate))
+                .context("couldn't register signal handler")?;
+        }
+
+        Ok(Self {
+            tick_rate,
+            last_tick: Instant::now(),
+            terminate,
+        })
+    }
+
+    // blocks until the next event, events the application
+    // doesn't handle like key releases are skipped
+    pub fn next(&mut self) -> anyhow::Result<AppEvent> {
+        loop {
+            if self.terminate.swap(false, Ordering::Relaxed) {
+                return Ok(AppEvent::Terminate);
+            }
+
+            let timeout = self.tick_rate.saturating_sub(self.last_tick.elapsed());
+            if !poll(timeout).context("couldn't poll for terminal events")? {
+                self.last_tick = Instant::now();
+                return Ok(AppEvent::Tick);
+            }
+
+            // raw mode turns Ctrl-C and Ctrl-Z into key presses
+            // instead of signals, so they are handled here
+            let event = match read().context("couldn't read terminal event")? {
+                Event::Key(key) if key.kind != KeyEventKind::Press => continue,
+                Event::Key(key) if is_ctrl(&key, 'c') => AppEvent::Terminate,
+                Event::Key(key) if is_ctrl(&key, 'z') => AppEvent::Suspend,
+                Event::Key(key) => AppEvent::Key(key),
+                Event::Mouse(mouse) if is_click_or_scroll(&mouse) => AppEvent::Mouse(mouse),
+                Event::Paste(text) => AppEvent::Paste(text),
+                Event::Resize(_, _) => AppEvent::Resize,
+                Event::FocusGained => AppEvent::FocusGained,
+                Event::FocusLost => AppEvent::FocusLost,
+                _ => continue,
+            };
+            return Ok(event);
+        }
+    }
+}
+
+fn is_ctrl(key: &KeyEvent, c: char) -> bool {
+    key.code == KeyCode::Char(c) && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+fn is_click_or_scroll(mouse: &MouseEvent) -> bool {
+    matches!(
+        mouse.kind,
+        MouseEventKind::Down(_) | MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+    )
+}
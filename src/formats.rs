@@ -0,0 +1,687 @@
+use anyhow::Context;
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{
+    widgets::{parse_tags, Priority, Repeat, TodoItem, TodoItemStatus, DATE_FORMAT},
+    workflow::Workflow,
+};
+
+// the file formats tasks can be imported from and exported to
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    TodoTxt,
+    Markdown,
+    Json,
+    Csv,
+}
+
+impl Format {
+    // the format matching the file extension, every `.txt` file is
+    // read as todo.txt
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "txt" => Some(Format::TodoTxt),
+            "md" | "markdown" => Some(Format::Markdown),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+
+    // the given format, or the one matching the extension of the path
+    pub fn resolve(format: Option<Format>, path: Option<&Path>) -> anyhow::Result<Format> {
+        if let Some(format) = format {
+            return Ok(format);
+        }
+        match path {
+            Some(path) => Format::from_path(path).with_context(|| {
+                format!(
+                    "can't tell the format of `{}`, expected a .txt, .md, .json or .csv file",
+                    path.display()
+                )
+            }),
+            None => anyhow::bail!("a format is needed when no file is given"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "todotxt" | "todo.txt" | "txt" => Ok(Format::TodoTxt),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            other => Err(format!(
+                "unknown format `{}`, expected todotxt, markdown, json or csv",
+                other
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::TodoTxt => write!(f, "todo.txt"),
+            Format::Markdown => write!(f, "markdown"),
+            Format::Json => write!(f, "json"),
+            Format::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+// writes the items in the format, subtasks keep the id of their parent
+pub fn export(format: Format, items: &[TodoItem]) -> anyhow::Result<String> {
+    match format {
+        Format::TodoTxt => Ok(export_todo_txt(items)),
+        Format::Markdown => Ok(export_markdown(items)),
+        Format::Json => {
+            let mut json =
+                serde_json::to_string_pretty(items).context("couldn't write the tasks as json")?;
+            json.push('\n');
+            Ok(json)
+        }
+        Format::Csv => export_csv(items),
+    }
+}
+
+// reads the items written in the format, the ids of the items only link
+// the subtasks to their parents and are replaced when added to a list
+pub fn import(format: Format, content: &str) -> anyhow::Result<Vec<TodoItem>> {
+    match format {
+        Format::TodoTxt => Ok(import_todo_txt(content)),
+        Format::Markdown => Ok(import_markdown(content)),
+        Format::Json => import_json(content),
+        Format::Csv => import_csv(content),
+    }
+}
+
+// exports the items to the file, in the given format or the one
+// matching the file extension
+pub fn export_file(path: &Path, format: Option<Format>, items: &[TodoItem]) -> anyhow::Result<()> {
+    let content = export(Format::resolve(format, Some(path))?, items)?;
+    std::fs::write(path, content).with_context(|| format!("couldn't write {}", path.display()))
+}
+
+// imports the items from the file, in the given format or the one
+// matching the file extension
+pub fn import_file(path: &Path, format: Option<Format>) -> anyhow::Result<Vec<TodoItem>> {
+    let format = Format::resolve(format, Some(path))?;
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("couldn't read {}", path.display()))?;
+    import(format, &content).with_context(|| format!("couldn't import {}", path.display()))
+}
+
+// the metadata written as terms after the title of a todo.txt or
+// markdown task
+#[derive(Default)]
+struct Terms {
+    tags: Vec<String>,
+    due: Option<NaiveDate>,
+    priority: Option<Priority>,
+    status: Option<String>,
+    repeat: Option<Repeat>,
+    id: Option<u64>,
+    parent: Option<u64>,
+}
+
+impl Terms {
+    // splits the `key:value` terms and the words starting with one of the
+    // tag prefixes out of the text, the remaining words are the title
+    fn parse(text: &str, tag_prefixes: &[char]) -> (String, Terms) {
+        let mut terms = Terms::default();
+        let mut title: Vec<&str> = Vec::new();
+        for word in text.split_whitespace() {
+            if let Some(tag) = word.strip_prefix(tag_prefixes) {
+                if !tag.is_empty() {
+                    terms.tags.extend(parse_tags(tag));
+                    continue;
+                }
+            }
+            let known = match word.split_once(':') {
+                Some(("due", value)) => NaiveDate::parse_from_str(value, DATE_FORMAT)
+                    .map(|due| terms.due = Some(due))
+                    .is_ok(),
+                Some(("priority", value)) => value
+                    .parse()
+                    .map(|priority| terms.priority = Some(priority))
+                    .is_ok(),
+                // the todo.txt priority of done tasks
+                Some(("pri", value)) => {
+                    let mut chars = value.chars();
+                    let priority = chars.next().and_then(letter_priority);
+                    terms.priority = terms.priority.or(priority);
+                    priority.is_some() && chars.next().is_none()
+                }
+                Some(("status", value)) if !value.is_empty() => {
+                    terms.status = Some(value.to_string());
+                    true
+                }
+                Some(("repeat", value)) => value
+                    .replace('-', " ")
+                    .parse()
+                    .map(|repeat| terms.repeat = Some(repeat))
+                    .is_ok(),
+                Some(("id", value)) => value.parse().map(|id| terms.id = Some(id)).is_ok(),
+                Some(("parent", value)) => value.parse().map(|id| terms.parent = Some(id)).is_ok(),
+                _ => false,
+            };
+            if !known {
+                title.push(word);
+            }
+        }
+        (title.join(" "), terms)
+    }
+
+    // the terms of the item that aren't written some other way by the
+    // format, the status is only written when the done mark can't tell it
+    fn of(item: &TodoItem) -> Vec<String> {
+        let mut terms = Vec::new();
+        if let Some(due) = item.due() {
+            terms.push(format!("due:{}", due.format(DATE_FORMAT)));
+        }
+        let implied = if item.is_done() {
+            TodoItemStatus::done()
+        } else {
+            TodoItemStatus::initial()
+        };
+        if *item.status() != implied {
+            terms.push(format!("status:{}", item.status().name().replace(' ', "-")));
+        }
+        if let Some(repeat) = item.repeat() {
+            terms.push(format!("repeat:{}", repeat.to_string().replace(' ', "-")));
+        }
+        terms
+    }
+
+    // builds the item, the status term wins over the done mark
+    fn into_item(self, title: String, description: String, done: bool) -> TodoItem {
+        let mut item = TodoItem::new(title, description);
+        let status = match &self.status {
+            Some(status) => status_named(status),
+            None if done => TodoItemStatus::done(),
+            None => TodoItemStatus::initial(),
+        };
+        item.set_status(status);
+        item.set_priority(self.priority.unwrap_or_default());
+        item.set_due(self.due);
+        item.set_tags(self.tags);
+        item.set_repeat(self.repeat);
+        item.set_id(self.id.unwrap_or_default());
+        item.set_parent(self.parent);
+        item
+    }
+}
+
+// the workflow status written with dashes in place of spaces, unknown
+// statuses are kept as they were written
+fn status_named(name: &str) -> TodoItemStatus {
+    let workflow = Workflow::current();
+    let status = workflow.statuses().iter().find(|status| {
+        status.name.eq_ignore_ascii_case(name)
+            || status.name.replace(' ', "-").eq_ignore_ascii_case(name)
+    });
+    match status {
+        Some(status) => TodoItemStatus::from(status.name.as_str()),
+        None => TodoItemStatus::from(name),
+    }
+}
+
+// imported dates without a time are taken as the start of the day
+fn start_of_day(date: NaiveDate) -> Option<DateTime<Local>> {
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+}
+
+// the ids of the items that have subtasks among the items
+fn parent_ids(items: &[TodoItem]) -> HashSet<u64> {
+    items.iter().filter_map(TodoItem::parent).collect()
+}
+
+// todo.txt lines look like `x 2024-05-02 2024-05-01 title +tag due:2024-05-10`
+// for done tasks and `(A) 2024-05-01 title` for the others, the description
+// has no place in the format and is left out
+fn export_todo_txt(items: &[TodoItem]) -> String {
+    let parents = parent_ids(items);
+    let mut out = String::new();
+    for item in items {
+        let mut words: Vec<String> = Vec::new();
+        if item.is_done() {
+            words.push("x".into());
+            let completed = item.completed().unwrap_or(item.updated());
+            words.push(completed.format(DATE_FORMAT).to_string());
+        } else if let Some(letter) = priority_letter(item.priority()) {
+            words.push(format!("({})", letter));
+        }
+        words.push(item.created().format(DATE_FORMAT).to_string());
+        words.push(item.title().clone());
+        words.extend(item.tags().iter().map(|tag| format!("+{}", tag)));
+        // done tasks lose the `(A)` prefix, the priority is kept as a term
+        if item.is_done() {
+            if let Some(letter) = priority_letter(item.priority()) {
+                words.push(format!("pri:{}", letter));
+            }
+        }
+        words.extend(Terms::of(item));
+        if parents.contains(&item.id()) {
+            words.push(format!("id:{}", item.id()));
+        }
+        if let Some(parent) = item.parent() {
+            words.push(format!("parent:{}", parent));
+        }
+        out.push_str(&words.join(" "));
+        out.push('\n');
+    }
+    out
+}
+
+fn priority_letter(priority: Priority) -> Option<char> {
+    match priority {
+        Priority::None => None,
+        Priority::Low => Some('C'),
+        Priority::Medium => Some('B'),
+        Priority::High => Some('A'),
+    }
+}
+
+// `A` is the highest priority, every letter after `C` is low
+fn letter_priority(letter: char) -> Option<Priority> {
+    match letter {
+        'A' => Some(Priority::High),
+        'B' => Some(Priority::Medium),
+        'C'..='Z' => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+fn import_todo_txt(content: &str) -> Vec<TodoItem> {
+    let mut items = Vec::new();
+    for line in content.lines() {
+        let mut rest = line.trim();
+        if rest.is_empty() {
+            continue;
+        }
+
+        let done = rest.starts_with("x ");
+        if done {
+            rest = rest[2..].trim_start();
+        }
+        let mut priority = None;
+        if let [b'(', letter, b')', b' ', ..] = rest.as_bytes() {
+            if let Some(p) = letter_priority(*letter as char) {
+                priority = Some(p);
+                rest = rest[4..].trim_start();
+            }
+        }
+        // done tasks may start with the completion date before the creation date
+        let mut dates = Vec::new();
+        while dates.len() < 1 + done as usize {
+            let Some((word, after)) = rest.split_once(' ') else {
+                break;
+            };
+            let Ok(date) = NaiveDate::parse_from_str(word, DATE_FORMAT) else {
+                break;
+            };
+            dates.push(date);
+            rest = after.trim_start();
+        }
+        let (completed, created) = match (done, dates.as_slice()) {
+            (true, [completed, created]) => (Some(*completed), Some(*created)),
+            (true, [completed]) => (Some(*completed), None),
+            (false, [created]) => (None, Some(*created)),
+            _ => (None, None),
+        };
+
+        let (title, mut terms) = Terms::parse(rest, &['+', '@']);
+        terms.priority = terms.priority.or(priority);
+        let mut item = terms.into_item(title, String::new(), done);
+        if let Some(created) = created.and_then(start_of_day) {
+            item.set_timestamps(created, completed.and_then(start_of_day));
+        }
+        items.push(item);
+    }
+    items
+}
+
+// markdown tasks are `- [ ]` checklist items with the tags written as
+// `#tag`, subtasks are nested under their parent and the description
+// follows on the lines indented under the task
+fn export_markdown(items: &[TodoItem]) -> String {
+    let ids: HashSet<u64> = items.iter().map(TodoItem::id).collect();
+    let mut children: HashMap<u64, Vec<&TodoItem>> = HashMap::new();
+    let mut roots = Vec::new();
+    for item in items {
+        match item.parent().filter(|parent| ids.contains(parent)) {
+            Some(parent) => children.entry(parent).or_default().push(item),
+            None => roots.push(item),
+        }
+    }
+
+    let mut out = String::new();
+    // the items are written depth first, the parents before their subtasks
+    let mut stack: Vec<(&TodoItem, usize)> = roots.into_iter().rev().map(|i| (i, 0)).collect();
+    let mut written = HashSet::new();
+    while let Some((item, depth)) = stack.pop() {
+        if !written.insert(item.id()) {
+            continue;
+        }
+        let indent = "  ".repeat(depth);
+        let mut words = vec![item.title().clone()];
+        words.extend(item.tags().iter().map(|tag| format!("#{}", tag)));
+        if item.priority() != Priority::None {
+            words.push(format!("priority:{}", item.priority()));
+        }
+        words.extend(Terms::of(item));
+        let check = if item.is_done() { 'x' } else { ' ' };
+        out.push_str(&format!("{}- [{}] {}\n", indent, check, words.join(" ")));
+        for line in item.description().lines() {
+            out.push_str(format!("{}  {}", indent, line).trim_end());
+            out.push('\n');
+        }
+
+        if let Some(subtasks) = children.get(&item.id()) {
+            stack.extend(subtasks.iter().rev().map(|child| (*child, depth + 1)));
+        }
+    }
+    out
+}
+
+// the done mark and the text of a checklist line
+fn parse_checkbox(line: &str) -> Option<(bool, &str)> {
+    let rest = line.strip_prefix(['-', '*', '+'])?.trim_start();
+    let done = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    Some((done, rest[3..].trim()))
+}
+
+// removes at most `width` leading spaces and tabs, other leading
+// whitespace is part of the text
+fn strip_indent(line: &str, width: usize) -> &str {
+    let start = line
+        .char_indices()
+        .take(width)
+        .find(|(_, c)| *c != ' ' && *c != '\t')
+        .map(|(i, _)| i)
+        .unwrap_or_else(|| line.chars().take(width).map(char::len_utf8).sum());
+    &line[start..]
+}
+
+fn import_markdown(content: &str) -> Vec<TodoItem> {
+    let mut items: Vec<TodoItem> = Vec::new();
+    let mut descriptions: Vec<Vec<String>> = Vec::new();
+    // the indentation and id of the tasks the next lines may be nested under
+    let mut open: Vec<(usize, u64)> = Vec::new();
+
+    for line in content.lines() {
+        let text = line.trim_start();
+        let indent = line.len() - text.len();
+        if let Some((done, text)) = parse_checkbox(text) {
+            while open.last().is_some_and(|(i, _)| *i >= indent) {
+                open.pop();
+            }
+            let (title, mut terms) = Terms::parse(text, &['#']);
+            let id = items.len() as u64 + 1;
+            terms.id = Some(id);
+            terms.parent = open.last().map(|(_, parent)| *parent);
+            items.push(terms.into_item(title, String::new(), done));
+            descriptions.push(Vec::new());
+            open.push((indent, id));
+            continue;
+        }
+
+        // lines indented under the last task are its description, blank
+        // lines are kept in case the description goes on after them
+        let Some((task_indent, _)) = open.last() else {
+            continue;
+        };
+        let description = descriptions.last_mut().unwrap();
+        if text.is_empty() {
+            if !description.is_empty() {
+                description.push(String::new());
+            }
+        } else if indent > *task_indent {
+            let text = strip_indent(line, task_indent + 2);
+            description.push(text.trim_end().to_string());
+        } else {
+            open.clear();
+        }
+    }
+
+    for (item, mut lines) in items.iter_mut().zip(descriptions) {
+        while lines.last().is_some_and(String::is_empty) {
+            lines.pop();
+        }
+        if !lines.is_empty() {
+            item.set_description(lines.join("\n"));
+        }
+    }
+    items
+}
+
+// json is either an array of tasks, as exported, or an object with the
+// tasks under `items` like the list data files
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonTasks {
+    Items(Vec<TodoItem>),
+    List { items: Vec<TodoItem> },
+}
+
+fn import_json(content: &str) -> anyhow::Result<Vec<TodoItem>> {
+    let tasks: JsonTasks = serde_json::from_str(content).context("couldn't read the json")?;
+    Ok(match tasks {
+        JsonTasks::Items(items) | JsonTasks::List { items } => items,
+    })
+}
+
+// a csv row, every column is optional when importing and the
+// tags are separated by spaces
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct CsvRecord {
+    id: u64,
+    parent: Option<u64>,
+    title: String,
+    description: String,
+    status: String,
+    priority: String,
+    due: Option<NaiveDate>,
+    tags: String,
+    repeat: String,
+    created: Option<DateTime<Local>>,
+    completed: Option<DateTime<Local>>,
+}
+
+fn export_csv(items: &[TodoItem]) -> anyhow::Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for item in items {
+        writer
+            .serialize(CsvRecord {
+                id: item.id(),
+                parent: item.parent(),
+                title: item.title().clone(),
+                description: item.description().clone(),
+                status: item.status().to_string(),
+                priority: item.priority().to_string(),
+                due: item.due(),
+                tags: item.tags().join(" "),
+                repeat: item.repeat().map(Repeat::to_string).unwrap_or_default(),
+                created: Some(item.created()),
+                completed: item.completed(),
+            })
+            .context("couldn't write the tasks as csv")?;
+    }
+    let bytes = writer
+        .into_inner()
+        .context("couldn't write the tasks as csv")?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+fn import_csv(content: &str) -> anyhow::Result<Vec<TodoItem>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let mut items = Vec::new();
+    for (i, record) in reader.deserialize::<CsvRecord>().enumerate() {
+        // the first line is the header
+        let row = i + 2;
+        let record = record.with_context(|| format!("couldn't read csv line {}", row))?;
+        let priority = record
+            .priority
+            .parse()
+            .map_err(|e| anyhow::anyhow!("csv line {}: {}", row, e))?;
+        let repeat = match record.repeat.as_str() {
+            "" => None,
+            repeat => Some(
+                repeat
+                    .parse()
+                    .map_err(|e| anyhow::anyhow!("csv line {}: {}", row, e))?,
+            ),
+        };
+
+        let mut item = TodoItem::new(record.title, record.description);
+        item.set_status(if record.status.is_empty() {
+            TodoItemStatus::initial()
+        } else {
+            status_named(&record.status)
+        });
+        item.set_priority(priority);
+        item.set_due(record.due);
+        item.set_tags(parse_tags(&record.tags));
+        item.set_repeat(repeat);
+        item.set_id(record.id);
+        item.set_parent(record.parent);
+        if let Some(created) = record.created {
+            item.set_timestamps(created, record.completed);
+        }
+        items.push(item);
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::TodoList;
+
+    // a parent with a custom status and every field set, and a done subtask
+    fn sample_items() -> Vec<TodoItem> {
+        let mut list = TodoList::default();
+        let mut parent = TodoItem::new("plan trip".into(), "book flights\nand hotels".into());
+        parent.set_status(TodoItemStatus::from("waiting"));
+        parent.set_priority(Priority::High);
+        parent.set_due(NaiveDate::from_ymd_opt(2026, 11, 2));
+        parent.set_tags(vec!["travel".into(), "family".into()]);
+        parent.set_repeat(Some("weekly mon,thu".parse().unwrap()));
+        let parent_id = list.add(parent);
+
+        let mut child = TodoItem::new("pack bags".into(), String::new());
+        child.set_status(TodoItemStatus::done());
+        child.set_priority(Priority::Low);
+        child.set_tags(vec!["packing".into()]);
+        list.add_child(parent_id, child);
+        list.items().to_vec()
+    }
+
+    // the exported items and the items imported back from them
+    fn round_trip(format: Format) -> (Vec<TodoItem>, Vec<TodoItem>) {
+        let original = sample_items();
+        let exported = export(format, &original).unwrap();
+        let imported = import(format, &exported).unwrap();
+        assert_eq!(imported.len(), 2, "{} lost tasks:\n{}", format, exported);
+        (original, imported)
+    }
+
+    // the fields every format keeps, with the subtask linked to its parent
+    fn assert_common_fields(format: Format, items: &[TodoItem]) {
+        let (parent, child) = (&items[0], &items[1]);
+        assert_eq!(parent.title(), "plan trip", "{}", format);
+        assert_eq!(parent.status().name(), "waiting", "{}", format);
+        assert_eq!(parent.priority().to_string(), "high", "{}", format);
+        assert_eq!(
+            parent.due(),
+            NaiveDate::from_ymd_opt(2026, 11, 2),
+            "{}",
+            format
+        );
+        assert_eq!(parent.tags(), ["travel", "family"], "{}", format);
+        assert_eq!(
+            parent.repeat().map(Repeat::to_string).as_deref(),
+            Some("weekly mon,thu"),
+            "{}",
+            format
+        );
+
+        assert_eq!(child.title(), "pack bags", "{}", format);
+        assert!(child.is_done(), "{}", format);
+        assert_eq!(child.priority().to_string(), "low", "{}", format);
+        assert_eq!(child.tags(), ["packing"], "{}", format);
+        assert_eq!(child.parent(), Some(parent.id()), "{}", format);
+    }
+
+    #[test]
+    fn json_keeps_everything() {
+        let (original, items) = round_trip(Format::Json);
+        assert_common_fields(Format::Json, &items);
+        assert_eq!(items[0].description(), "book flights\nand hotels");
+
+        assert!(items[0].created() == original[0].created());
+        assert!(items[1].completed() == original[1].completed());
+    }
+
+    #[test]
+    fn csv_keeps_everything() {
+        let (original, items) = round_trip(Format::Csv);
+        assert_common_fields(Format::Csv, &items);
+        assert_eq!(items[0].description(), "book flights\nand hotels");
+
+        assert!(items[0].created() == original[0].created());
+        assert!(items[1].completed() == original[1].completed());
+    }
+
+    #[test]
+    fn todo_txt_keeps_the_metadata_but_not_the_description() {
+        let (original, items) = round_trip(Format::TodoTxt);
+        assert_common_fields(Format::TodoTxt, &items);
+        assert_eq!(items[0].description(), "");
+
+        // the dates are kept, without the time of day
+        assert_eq!(
+            items[0].created().date_naive(),
+            original[0].created().date_naive()
+        );
+        assert_eq!(
+            items[1].completed().map(|c| c.date_naive()),
+            original[1].completed().map(|c| c.date_naive())
+        );
+    }
+
+    #[test]
+    fn markdown_keeps_the_metadata_and_description() {
+        let (_, items) = round_trip(Format::Markdown);
+        assert_common_fields(Format::Markdown, &items);
+        assert_eq!(items[0].description(), "book flights\nand hotels");
+    }
+
+    #[test]
+    fn markdown_description_keeps_non_ascii_indentation() {
+        let items = import(Format::Markdown, "- [ ] task\n\u{3000}desc\n\t\u{a0}more\n").unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].description(), "\u{3000}desc\n\u{a0}more");
+    }
+}
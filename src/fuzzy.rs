@@ -0,0 +1,49 @@
+// score bonuses used to rank the matches, consecutive matched chars
+// and chars at the start of words are preferred over scattered ones
+const MATCH_SCORE: i64 = 1;
+const CONSECUTIVE_BONUS: i64 = 4;
+const WORD_START_BONUS: i64 = 3;
+
+pub struct FuzzyMatch {
+    pub score: i64,
+    // char indexes of the matched chars in the matched text
+    pub indices: Vec<usize>,
+}
+
+// matches the pattern against the text as a case insensitive
+// subsequence, returns `None` if not every pattern char is found
+// in order, an empty pattern matches everything
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let mut pattern_chars = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+    let mut score = 0;
+    let mut indices = Vec::new();
+    let mut prev: Option<char> = None;
+
+    for (i, c) in text.chars().enumerate() {
+        let Some(&wanted) = pattern_chars.peek() else {
+            break;
+        };
+
+        if c.to_lowercase().eq(std::iter::once(wanted)) {
+            score += MATCH_SCORE;
+            if indices.last().is_some_and(|&last| last + 1 == i) {
+                score += CONSECUTIVE_BONUS;
+            }
+            if prev.is_none_or(|p| !p.is_alphanumeric()) {
+                score += WORD_START_BONUS;
+            }
+            indices.push(i);
+            pattern_chars.next();
+        }
+        prev = Some(c);
+    }
+
+    if pattern_chars.peek().is_some() {
+        return None;
+    }
+    Some(FuzzyMatch { score, indices })
+}
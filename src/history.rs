@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+
+// number of undo steps kept by default
+const DEFAULT_LIMIT: usize = 100;
+
+// bounded undo/redo stacks of snapshots, a snapshot is the
+// state before a change, the oldest snapshots are dropped
+// once the limit is reached
+pub struct History<T> {
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    limit: usize,
+}
+
+impl<T> History<T> {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit,
+        }
+    }
+
+    // records the state before a change, a new change
+    // makes the undone changes unreachable
+    pub fn record(&mut self, snapshot: T) {
+        if self.undo.len() == self.limit {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(snapshot);
+        self.redo.clear();
+    }
+
+    // returns the state to restore, `current` is kept so the undo can be redone
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let snapshot = self.undo.pop_back()?;
+        self.redo.push(current);
+        Some(snapshot)
+    }
+
+    // returns the state to restore, `current` is kept so the redo can be undone
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let snapshot = self.redo.pop()?;
+        self.undo.push_back(current);
+        Some(snapshot)
+    }
+
+    // the snapshot the next undo restores
+    pub fn next_undo(&self) -> Option<&T> {
+        self.undo.back()
+    }
+
+    // the snapshot the next redo restores
+    pub fn next_redo(&self) -> Option<&T> {
+        self.redo.last()
+    }
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_LIMIT)
+    }
+}
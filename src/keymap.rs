@@ -0,0 +1,733 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::config;
+
+// the part of the application a binding applies to, global bindings
+// are checked first while not writing, so they shadow the view bindings
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Context {
+    Global,
+    List,
+    Search,
+    Form,
+    Switcher,
+    // checked before the list bindings while selecting a range of rows
+    Visual,
+    // the `:` command line
+    Command,
+    // the notification log view
+    Notifications,
+    // the calendar view
+    Calendar,
+    // the help popup with the whole keymap, it takes every key while open
+    Help,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    NewTask,
+    ListTasks,
+    Calendar,
+    Notifications,
+    DismissNotification,
+    ClearNotifications,
+    PinNotification,
+    Help,
+    Quit,
+    Down,
+    Up,
+    NextStatus,
+    PrevStatus,
+    Edit,
+    Delete,
+    Undo,
+    Redo,
+    Search,
+    Sort,
+    Group,
+    AddSubtask,
+    Fold,
+    Unfold,
+    OpenList,
+    MoveToList,
+    Top,
+    Bottom,
+    Visual,
+    CommandLine,
+    ToggleMark,
+    MarkAll,
+    MarkMatching,
+    ClearMarks,
+    Tag,
+    MoveDown,
+    MoveUp,
+    MoveToTop,
+    MoveToBottom,
+    Snooze,
+    PrevDay,
+    NextDay,
+    PrevMonth,
+    NextMonth,
+    Today,
+    NewList,
+    NextField,
+    Complete,
+    Submit,
+    Cancel,
+}
+
+// a key with its modifiers, shift is part of the char for
+// char keys so it is not kept as a modifier for them
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+// the resolved bindings of every context, in the order
+// they are shown in the controls line
+#[derive(Clone)]
+pub struct Keymap {
+    bindings: HashMap<Context, Vec<(Action, Vec<KeyBinding>)>>,
+    // conflicting or invalid bindings found while loading,
+    // reported to the user at startup
+    conflicts: Vec<String>,
+}
+
+// the bindings as written in the config file, action names
+// mapped to key names for every context
+type KeymapConfig = HashMap<Context, HashMap<Action, Vec<String>>>;
+
+impl Context {
+    pub const ALL: [Context; 10] = [
+        Context::Global,
+        Context::List,
+        Context::Search,
+        Context::Form,
+        Context::Switcher,
+        Context::Visual,
+        Context::Command,
+        Context::Notifications,
+        Context::Calendar,
+        Context::Help,
+    ];
+
+    // the actions that can be bound in the context, in display order
+    fn actions(self) -> &'static [Action] {
+        match self {
+            Context::Global => &[
+                Action::NewTask,
+                Action::ListTasks,
+                Action::Calendar,
+                Action::Notifications,
+                Action::DismissNotification,
+                Action::ClearNotifications,
+                Action::PinNotification,
+                Action::Help,
+                Action::Quit,
+            ],
+            Context::List => &[
+                Action::Up,
+                Action::Down,
+                Action::NextStatus,
+                Action::PrevStatus,
+                Action::Edit,
+                Action::Delete,
+                Action::Undo,
+                Action::Redo,
+                Action::Search,
+                Action::Sort,
+                Action::Group,
+                Action::AddSubtask,
+                Action::Fold,
+                Action::Unfold,
+                Action::OpenList,
+                Action::MoveToList,
+                Action::Top,
+                Action::Bottom,
+                Action::Visual,
+                Action::CommandLine,
+                Action::ToggleMark,
+                Action::MarkAll,
+                Action::MarkMatching,
+                Action::ClearMarks,
+                Action::Tag,
+                Action::MoveDown,
+                Action::MoveUp,
+                Action::MoveToTop,
+                Action::MoveToBottom,
+                Action::Snooze,
+            ],
+            Context::Search => &[Action::Up, Action::Down, Action::Submit, Action::Cancel],
+            Context::Form => &[Action::NextField, Action::Submit, Action::Cancel],
+            Context::Switcher => &[
+                Action::Up,
+                Action::Down,
+                Action::NewList,
+                Action::Submit,
+                Action::Cancel,
+            ],
+            Context::Visual => &[Action::Cancel],
+            Context::Command => &[Action::Complete, Action::Submit, Action::Cancel],
+            Context::Notifications => &[
+                Action::Up,
+                Action::Down,
+                Action::Top,
+                Action::Bottom,
+                Action::PinNotification,
+            ],
+            Context::Calendar => &[
+                Action::PrevDay,
+                Action::NextDay,
+                Action::Up,
+                Action::Down,
+                Action::PrevMonth,
+                Action::NextMonth,
+                Action::Today,
+            ],
+            Context::Help => &[Action::Up, Action::Down, Action::Cancel],
+        }
+    }
+
+    // bindings used when the config doesn't set the action
+    fn default_keys(self, action: Action) -> &'static [&'static str] {
+        match (self, action) {
+            (Context::Global, Action::NewTask) => &["n"],
+            (Context::Global, Action::ListTasks) => &["l"],
+            (Context::Global, Action::Calendar) => &["c"],
+            (Context::Global, Action::Notifications) => &["N"],
+            (Context::Global, Action::DismissNotification) => &["x"],
+            (Context::Global, Action::ClearNotifications) => &["ctrl-x"],
+            (Context::Global, Action::PinNotification) => &["p"],
+            (Context::Global, Action::Help) => &["?"],
+            (Context::Global, Action::Quit) => &["q", "esc"],
+            (Context::List, Action::Up) => &["k", "up"],
+            (Context::List, Action::Down) => &["j", "down"],
+            (Context::List, Action::NextStatus) => &["tab", "enter"],
+            (Context::List, Action::PrevStatus) => &["backtab"],
+            (Context::List, Action::Edit) => &["e"],
+            (Context::List, Action::Delete) => &["d", "delete"],
+            (Context::List, Action::Undo) => &["u"],
+            (Context::List, Action::Redo) => &["ctrl-r"],
+            (Context::List, Action::Search) => &["/"],
+            (Context::List, Action::Sort) => &["s"],
+            (Context::List, Action::Group) => &["b"],
+            (Context::List, Action::AddSubtask) => &["a"],
+            (Context::List, Action::Fold) => &["h", "left"],
+            (Context::List, Action::Unfold) => &["right"],
+            (Context::List, Action::OpenList) => &["o"],
+            (Context::List, Action::MoveToList) => &["m"],
+            (Context::List, Action::Top) => &["g", "home"],
+            (Context::List, Action::Bottom) => &["G", "end"],
+            (Context::List, Action::Visual) => &["v"],
+            (Context::List, Action::CommandLine) => &[":"],
+            (Context::List, Action::ToggleMark) => &["space"],
+            (Context::List, Action::MarkAll) => &["A"],
+            (Context::List, Action::MarkMatching) => &["*"],
+            (Context::List, Action::ClearMarks) => &["X"],
+            (Context::List, Action::Tag) => &["t"],
+            (Context::List, Action::MoveDown) => &["J"],
+            (Context::List, Action::MoveUp) => &["K"],
+            (Context::List, Action::MoveToTop) => &["T"],
+            (Context::List, Action::MoveToBottom) => &["B"],
+            (Context::List, Action::Snooze) => &["z"],
+            (Context::Search, Action::Up) => &["up"],
+            (Context::Search, Action::Down) => &["down"],
+            (Context::Search, Action::Submit) => &["enter"],
+            (Context::Search, Action::Cancel) => &["esc"],
+            (Context::Form, Action::NextField) => &["tab"],
+            (Context::Form, Action::Submit) => &["enter"],
+            (Context::Form, Action::Cancel) => &["esc"],
+            (Context::Switcher, Action::Up) => &["k", "up"],
+            (Context::Switcher, Action::Down) => &["j", "down"],
+            (Context::Switcher, Action::NewList) => &["c"],
+            (Context::Switcher, Action::Submit) => &["enter"],
+            (Context::Switcher, Action::Cancel) => &["esc"],
+            (Context::Visual, Action::Cancel) => &["esc", "v"],
+            (Context::Command, Action::Complete) => &["tab"],
+            (Context::Command, Action::Submit) => &["enter"],
+            (Context::Command, Action::Cancel) => &["esc"],
+            (Context::Notifications, Action::Up) => &["k", "up"],
+            (Context::Notifications, Action::Down) => &["j", "down"],
+            (Context::Notifications, Action::Top) => &["home"],
+            (Context::Notifications, Action::Bottom) => &["G", "end"],
+            (Context::Notifications, Action::PinNotification) => &["enter", "space"],
+            (Context::Calendar, Action::PrevDay) => &["h", "left"],
+            (Context::Calendar, Action::NextDay) => &["right"],
+            (Context::Calendar, Action::Up) => &["k", "up"],
+            (Context::Calendar, Action::Down) => &["j", "down"],
+            (Context::Calendar, Action::PrevMonth) => &["<", "pageup"],
+            (Context::Calendar, Action::NextMonth) => &[">", "pagedown"],
+            (Context::Calendar, Action::Today) => &["t", "home"],
+            (Context::Help, Action::Up) => &["k", "up"],
+            (Context::Help, Action::Down) => &["j", "down"],
+            (Context::Help, Action::Cancel) => &["esc", "?", "q"],
+            _ => &[],
+        }
+    }
+}
+
+impl Action {
+    // vim style actions whose char key has to be pressed twice,
+    // like `dd` and `gg`, other keys bound to them act right away
+    pub fn doubled(self) -> bool {
+        matches!(self, Action::Delete | Action::Top)
+    }
+
+    // the actions shown in the controls line, the others are only
+    // listed in the help popup, the controls line adds the help itself
+    fn main(self, context: Context) -> bool {
+        match context {
+            Context::Global => matches!(self, Action::NewTask | Action::Quit),
+            Context::List => matches!(
+                self,
+                Action::NextStatus
+                    | Action::Edit
+                    | Action::Delete
+                    | Action::Undo
+                    | Action::Search
+                    | Action::AddSubtask
+                    | Action::OpenList
+                    | Action::CommandLine
+            ),
+            _ => true,
+        }
+    }
+
+    // the text shown next to the keys in the controls line
+    pub fn description(self, context: Context) -> &'static str {
+        match (self, context) {
+            (Action::NewTask, _) => "new task",
+            (Action::ListTasks, _) => "list tasks",
+            (Action::Calendar, _) => "calendar",
+            (Action::Notifications, _) => "notification log",
+            (Action::DismissNotification, _) => "dismiss notification",
+            (Action::ClearNotifications, _) => "clear notifications",
+            (Action::PinNotification, Context::Notifications) => "pin/unpin",
+            (Action::PinNotification, _) => "pin notification",
+            (Action::Help, _) => "help",
+            (Action::Quit, _) => "quit",
+            (Action::Down, Context::Calendar) => "next week",
+            (Action::Up, Context::Calendar) => "prev week",
+            (Action::Down, _) => "down",
+            (Action::Up, _) => "up",
+            (Action::NextStatus, _) => "next status/fold group",
+            (Action::PrevStatus, _) => "prev status",
+            (Action::Edit, _) => "edit task",
+            (Action::Delete, _) => "delete task",
+            (Action::Undo, _) => "undo",
+            (Action::Redo, _) => "redo",
+            (Action::Search, _) => "search",
+            (Action::Sort, _) => "sort",
+            (Action::Group, _) => "group",
+            (Action::AddSubtask, _) => "add subtask",
+            (Action::Fold, _) => "fold",
+            (Action::Unfold, _) => "unfold",
+            (Action::OpenList, _) => "open list",
+            (Action::MoveToList, _) => "move to list",
+            (Action::Top, _) => "top",
+            (Action::Bottom, _) => "bottom",
+            (Action::Visual, _) => "select range",
+            (Action::CommandLine, _) => "command",
+            (Action::ToggleMark, _) => "mark",
+            (Action::MarkAll, _) => "mark all",
+            (Action::MarkMatching, _) => "mark matching",
+            (Action::ClearMarks, _) => "clear marks",
+            (Action::Tag, _) => "tag",
+            (Action::MoveDown, _) => "move task down",
+            (Action::MoveUp, _) => "move task up",
+            (Action::MoveToTop, _) => "move task to top",
+            (Action::MoveToBottom, _) => "move task to bottom",
+            (Action::Snooze, _) => "snooze reminders",
+            (Action::PrevDay, _) => "prev day",
+            (Action::NextDay, _) => "next day",
+            (Action::PrevMonth, _) => "prev month",
+            (Action::NextMonth, _) => "next month",
+            (Action::Today, _) => "today",
+            (Action::NewList, _) => "create list",
+            (Action::NextField, _) => "next field",
+            (Action::Complete, _) => "complete",
+            (Action::Submit, Context::Search) => "keep filter",
+            (Action::Submit, Context::Switcher) => "select",
+            (Action::Submit, Context::Command) => "run",
+            (Action::Submit, _) => "save task",
+            (Action::Cancel, Context::Search) => "clear filter",
+            (Action::Cancel, Context::Visual) => "stop selecting",
+            (Action::Cancel, Context::Help) => "close",
+            (Action::Cancel, _) => "cancel",
+        }
+    }
+}
+
+impl Keymap {
+    // the keymap used by the application
+    pub fn current() -> &'static Keymap {
+        &config::get().keymap
+    }
+
+    pub fn conflicts(&self) -> &[String] {
+        &self.conflicts
+    }
+
+    // the action bound to the key in the context
+    pub fn action(&self, context: Context, key: &KeyEvent) -> Option<Action> {
+        let key = KeyBinding::from(key);
+        self.bindings
+            .get(&context)?
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(action, _)| *action)
+    }
+
+    // the keys and descriptions of the context actions as
+    // `(keys, description)` pairs, for the help popup
+    pub fn controls(&self, context: Context) -> Vec<(String, &'static str)> {
+        self.bound(context)
+            .map(|(action, keys)| (keys, action.description(context)))
+            .collect()
+    }
+
+    // the controls of the main actions of the context, for the controls line
+    pub fn main_controls(&self, context: Context) -> Vec<(String, &'static str)> {
+        self.bound(context)
+            .filter(|(action, _)| action.main(context))
+            .map(|(action, keys)| (keys, action.description(context)))
+            .collect()
+    }
+
+    // the bound actions of the context with their keys as shown to the user
+    fn bound(&self, context: Context) -> impl Iterator<Item = (Action, String)> + '_ {
+        self.bindings
+            .get(&context)
+            .into_iter()
+            .flatten()
+            .filter(|(_, keys)| !keys.is_empty())
+            .map(|(action, keys)| (*action, Keymap::keys_text(*action, keys)))
+    }
+
+    // the keys bound to the action in the context as shown in the
+    // controls line, like `u` or `dd/DEL`, `None` when it is unbound
+    pub fn keys(&self, context: Context, action: Action) -> Option<String> {
+        let (_, keys) = self
+            .bindings
+            .get(&context)?
+            .iter()
+            .find(|(a, _)| *a == action)?;
+        (!keys.is_empty()).then(|| Keymap::keys_text(action, keys))
+    }
+
+    fn keys_text(action: Action, keys: &[KeyBinding]) -> String {
+        let keys: Vec<String> = keys
+            .iter()
+            .map(|key| match key.plain_char() {
+                Some(c) if action.doubled() => format!("{}{}", c, c),
+                _ => key.to_string(),
+            })
+            .collect();
+        keys.join("/")
+    }
+
+    // builds the keymap from the defaults with the configured bindings
+    // replacing the default bindings of the same action, an action with
+    // an invalid key keeps its default bindings and the key is reported
+    fn build(config: KeymapConfig) -> Self {
+        let mut conflicts = Vec::new();
+        let mut bindings = HashMap::new();
+
+        for (context, actions) in &config {
+            for action in actions.keys() {
+                if !context.actions().contains(action) {
+                    conflicts.push(format!(
+                        "action `{}` can't be bound in `{}`",
+                        action, context
+                    ));
+                }
+            }
+        }
+
+        for context in Context::ALL {
+            let configured = config.get(&context);
+            let mut context_bindings = Vec::new();
+
+            for action in context.actions() {
+                let keys = configured
+                    .and_then(|actions| actions.get(action))
+                    .and_then(|keys| {
+                        let parsed: Result<Vec<KeyBinding>, _> =
+                            keys.iter().map(|k| k.parse()).collect();
+                        if parsed.is_err() {
+                            for key in keys.iter().filter(|k| k.parse::<KeyBinding>().is_err()) {
+                                conflicts.push(format!(
+                                    "invalid key `{}` for `{}` in `{}`",
+                                    key, action, context
+                                ));
+                            }
+                        }
+                        parsed.ok()
+                    })
+                    .unwrap_or_else(|| {
+                        context
+                            .default_keys(*action)
+                            .iter()
+                            .map(|k| k.parse().expect("default key bindings are valid"))
+                            .collect()
+                    });
+                context_bindings.push((*action, keys));
+            }
+            bindings.insert(context, context_bindings);
+        }
+
+        let mut keymap = Self {
+            bindings,
+            conflicts,
+        };
+        keymap.conflicts.extend(keymap.find_conflicts());
+        keymap
+    }
+
+    // keys bound to more than one action in the same context, and view
+    // keys that can't be reached because a global binding uses them
+    fn find_conflicts(&self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        let global = &self.bindings[&Context::Global];
+
+        for context in Context::ALL {
+            let bindings = &self.bindings[&context];
+
+            for (i, (action, keys)) in bindings.iter().enumerate() {
+                for key in keys {
+                    for (other, other_keys) in &bindings[i + 1..] {
+                        if other_keys.contains(key) {
+                            conflicts.push(format!(
+                                "`{}` is bound to both `{}` and `{}` in `{}`",
+                                key, action, other, context
+                            ));
+                        }
+                    }
+
+                    // the other contexts are used while writing or in the
+                    // visual and command modes, when the global bindings are disabled
+                    if !matches!(
+                        context,
+                        Context::List | Context::Notifications | Context::Calendar
+                    ) {
+                        continue;
+                    }
+                    if let Some((other, _)) = global.iter().find(|(_, g)| g.contains(key)) {
+                        conflicts.push(format!(
+                            "`{}` of `{}` in `{}` is shadowed by the global `{}`",
+                            key, action, context, other
+                        ));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::build(KeymapConfig::new())
+    }
+}
+
+impl<'de> Deserialize<'de> for Keymap {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let config = KeymapConfig::deserialize(deserializer)?;
+        Ok(Keymap::build(config))
+    }
+}
+
+impl From<&KeyEvent> for KeyBinding {
+    fn from(key: &KeyEvent) -> Self {
+        KeyBinding::new(key.code, key.modifiers)
+    }
+}
+
+impl KeyBinding {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers }
+    }
+
+    // the char of a char key pressed without ctrl or alt
+    fn plain_char(&self) -> Option<char> {
+        match self.code {
+            KeyCode::Char(c) if self.modifiers.is_empty() => Some(c),
+            _ => None,
+        }
+    }
+}
+
+// the char of the key if it was pressed without ctrl or alt,
+// used for counts and for the keys of doubled actions
+pub fn plain_char(key: &KeyEvent) -> Option<char> {
+    KeyBinding::from(key).plain_char()
+}
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    // parses keys like `j`, `J`, `ctrl-r`, `alt-enter`, `shift-tab` or `f1`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+
+        while let Some((modifier, key)) = rest.split_once('-').filter(|(_, key)| !key.is_empty()) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "c" => KeyModifiers::CONTROL,
+                "alt" | "m" => KeyModifiers::ALT,
+                "shift" | "s" => KeyModifiers::SHIFT,
+                _ => break,
+            };
+            rest = key;
+        }
+
+        let code = match rest.to_lowercase().as_str() {
+            _ if rest.chars().count() == 1 => KeyCode::Char(rest.chars().next().unwrap()),
+            "space" => KeyCode::Char(' '),
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "backspace" | "bs" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "pagedown" | "pgdn" => KeyCode::PageDown,
+            f if f.starts_with('f') && f[1..].parse::<u8>().is_ok() => {
+                KeyCode::F(f[1..].parse().unwrap())
+            }
+            _ => return Err(format!("unknown key `{}`", s)),
+        };
+        Ok(KeyBinding::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "C-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "M-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "S-")?;
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "SPACE"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Tab => write!(f, "TAB"),
+            KeyCode::BackTab => write!(f, "S-TAB"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Backspace => write!(f, "BS"),
+            KeyCode::Delete => write!(f, "DEL"),
+            KeyCode::Insert => write!(f, "INS"),
+            KeyCode::Up => write!(f, "UP"),
+            KeyCode::Down => write!(f, "DN"),
+            KeyCode::Left => write!(f, "LEFT"),
+            KeyCode::Right => write!(f, "RIGHT"),
+            KeyCode::Home => write!(f, "HOME"),
+            KeyCode::End => write!(f, "END"),
+            KeyCode::PageUp => write!(f, "PGUP"),
+            KeyCode::PageDown => write!(f, "PGDN"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            _ => write!(f, "?"),
+        }
+    }
+}
+
+impl fmt::Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Context::Global => write!(f, "global"),
+            Context::List => write!(f, "list"),
+            Context::Search => write!(f, "search"),
+            Context::Form => write!(f, "form"),
+            Context::Switcher => write!(f, "switcher"),
+            Context::Visual => write!(f, "visual"),
+            Context::Command => write!(f, "command"),
+            Context::Notifications => write!(f, "notifications"),
+            Context::Calendar => write!(f, "calendar"),
+            Context::Help => write!(f, "help"),
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Action::NewTask => "new_task",
+            Action::ListTasks => "list_tasks",
+            Action::Calendar => "calendar",
+            Action::Notifications => "notifications",
+            Action::DismissNotification => "dismiss_notification",
+            Action::ClearNotifications => "clear_notifications",
+            Action::PinNotification => "pin_notification",
+            Action::Help => "help",
+            Action::Quit => "quit",
+            Action::Down => "down",
+            Action::Up => "up",
+            Action::NextStatus => "next_status",
+            Action::PrevStatus => "prev_status",
+            Action::Edit => "edit",
+            Action::Delete => "delete",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::Search => "search",
+            Action::Sort => "sort",
+            Action::Group => "group",
+            Action::AddSubtask => "add_subtask",
+            Action::Fold => "fold",
+            Action::Unfold => "unfold",
+            Action::OpenList => "open_list",
+            Action::MoveToList => "move_to_list",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::Visual => "visual",
+            Action::CommandLine => "command_line",
+            Action::ToggleMark => "toggle_mark",
+            Action::MarkAll => "mark_all",
+            Action::MarkMatching => "mark_matching",
+            Action::ClearMarks => "clear_marks",
+            Action::Tag => "tag",
+            Action::MoveDown => "move_down",
+            Action::MoveUp => "move_up",
+            Action::MoveToTop => "move_to_top",
+            Action::MoveToBottom => "move_to_bottom",
+            Action::Snooze => "snooze",
+            Action::PrevDay => "prev_day",
+            Action::NextDay => "next_day",
+            Action::PrevMonth => "prev_month",
+            Action::NextMonth => "next_month",
+            Action::Today => "today",
+            Action::NewList => "new_list",
+            Action::NextField => "next_field",
+            Action::Complete => "complete",
+            Action::Submit => "submit",
+            Action::Cancel => "cancel",
+        };
+        write!(f, "{}", name)
+    }
+}
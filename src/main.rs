@@ -1,44 +1,118 @@
 mod app;
+mod cli;
+mod commands;
+mod config;
+mod events;
+mod formats;
+mod fuzzy;
+mod history;
+mod keymap;
+mod reminders;
+mod storage;
+mod theme;
 mod views;
 mod widgets;
+mod workflow;
 use app::Application;
+use clap::Parser;
+use cli::Cli;
+use config::Config;
+use storage::Storage;
 
 mod tui {
     use anyhow::Context;
     use crossterm::ExecutableCommand;
     use ratatui::{
         backend::{Backend, CrosstermBackend},
-        crossterm::terminal::{
-            disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+        crossterm::{
+            event::{
+                DisableBracketedPaste, DisableFocusChange, DisableMouseCapture,
+                EnableBracketedPaste, EnableFocusChange, EnableMouseCapture,
+            },
+            terminal::{
+                disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+            },
         },
         Terminal,
     };
     use std::io::stdout;
 
-    pub fn load_terminal() -> anyhow::Result<Terminal<impl Backend>> {
-        stdout()
-            .execute(EnterAlternateScreen)
-            .context("couldn't enter alternated screen")?;
-        enable_raw_mode().context("couldn't enter terminal raw mode")?;
+    // restores the terminal when dropped, so the terminal is restored
+    // even when the application returns early with an error
+    pub struct TerminalGuard;
+
+    pub fn load_terminal() -> anyhow::Result<(Terminal<impl Backend>, TerminalGuard)> {
+        install_panic_hook();
+        enter()?;
         let terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
-        Ok(terminal)
+        Ok((terminal, TerminalGuard))
     }
 
     pub fn restore_terminal() -> anyhow::Result<()> {
         disable_raw_mode().context("couldn't disable raw mode")?;
+        stdout()
+            .execute(DisableBracketedPaste)?
+            .execute(DisableFocusChange)?
+            .execute(DisableMouseCapture)
+            .context("couldn't disable paste, focus and mouse events")?;
         stdout()
             .execute(LeaveAlternateScreen)
             .context("couldn't leave alternated screen")?;
         Ok(())
     }
+
+    // gives the terminal back to the shell and stops the process like
+    // the shell would on Ctrl-Z, the terminal is set up again once the
+    // process is resumed with `fg`, the screen must be redrawn after
+    pub fn suspend() -> anyhow::Result<()> {
+        restore_terminal()?;
+        signal_hook::low_level::raise(signal_hook::consts::SIGTSTP)
+            .context("couldn't suspend the process")?;
+        enter()
+    }
+
+    fn enter() -> anyhow::Result<()> {
+        stdout()
+            .execute(EnterAlternateScreen)
+            .context("couldn't enter alternated screen")?;
+        enable_raw_mode().context("couldn't enter terminal raw mode")?;
+        stdout()
+            .execute(EnableBracketedPaste)?
+            .execute(EnableFocusChange)?
+            .execute(EnableMouseCapture)
+            .context("couldn't enable paste, focus and mouse events")?;
+        Ok(())
+    }
+
+    // restores the terminal before the panic message is printed,
+    // otherwise the message is lost on the alternate screen
+    fn install_panic_hook() {
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let _ = restore_terminal();
+            hook(info);
+        }));
+    }
+
+    impl Drop for TerminalGuard {
+        fn drop(&mut self) {
+            let _ = restore_terminal();
+        }
+    }
 }
 
 fn main() -> anyhow::Result<()> {
-    let terminal = tui::load_terminal()?;
-    let mut application = Application::default();
+    let cli = Cli::parse();
+    config::init(Config::load()?);
+    let storage = Storage::from_env()?;
+
+    if let Some(command) = cli.command {
+        return cli::run(&cli.list, command, &storage);
+    }
+
+    let mut application = Application::new(storage);
+    application.init()?;
 
-    application.init();
-    application.run(terminal)?;
-    tui::restore_terminal()?;
-    Ok(())
+    let (terminal, _guard) = tui::load_terminal()?;
+    application.run(terminal)
 }
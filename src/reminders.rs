@@ -0,0 +1,190 @@
+use chrono::{Days, Local, NaiveDate};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    config,
+    widgets::{Notification, NotificationLevel, TodoItem, DATE_FORMAT},
+};
+
+// how often the due dates are checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+// more reminders than this at once are shown as a notification per kind
+const MAX_SEPARATE: usize = 3;
+// how long the reminder notifications are shown
+const REMINDER_DURATION: Duration = Duration::from_secs(8);
+
+// the `[reminders]` table of the config
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReminderConfig {
+    pub enabled: bool,
+    // tasks due within this many days are due soon, 0 only reminds of the tasks due today
+    pub due_soon_days: u32,
+    // how long the reminders of snoozed tasks are held back
+    pub snooze_minutes: u64,
+}
+
+// a reminder is shown once when a task is due soon and once
+// more when it becomes overdue
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Kind {
+    DueSoon,
+    Overdue,
+}
+
+// a task of a list, ids are only unique inside their list
+type Task = (String, u64);
+
+// keeps track of the reminders shown for the tasks of every list
+#[derive(Default)]
+pub struct Reminders {
+    // the due date each reminder was shown for, moving the due date reminds again
+    shown: HashMap<(Task, Kind), NaiveDate>,
+    // the tasks that are not reminded of until the instant
+    snoozed: HashMap<Task, Instant>,
+    last_check: Option<Instant>,
+}
+
+// a task that should be reminded of
+struct Reminder {
+    kind: Kind,
+    title: String,
+    due: NaiveDate,
+    // the list of the task when it isn't the active list
+    list: Option<String>,
+}
+
+impl ReminderConfig {
+    pub fn current() -> &'static ReminderConfig {
+        &config::get().reminders
+    }
+}
+
+impl Default for ReminderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            due_soon_days: 1,
+            snooze_minutes: 60,
+        }
+    }
+}
+
+impl Reminders {
+    // true when reminders are enabled and the last check was long enough ago
+    pub fn should_check(&self) -> bool {
+        ReminderConfig::current().enabled
+            && self
+                .last_check
+                .is_none_or(|last| last.elapsed() >= CHECK_INTERVAL)
+    }
+
+    // the notifications for the unfinished tasks that are due soon or overdue
+    // and weren't reminded of yet, the lists are given with their name and
+    // whether they are the active list
+    pub fn check<'a>(
+        &mut self,
+        lists: impl Iterator<Item = (&'a str, &'a [TodoItem], bool)>,
+    ) -> Vec<Notification> {
+        let now = Instant::now();
+        self.last_check = Some(now);
+        self.snoozed.retain(|_, until| *until > now);
+
+        let today = Local::now().date_naive();
+        let soon = today + Days::new(ReminderConfig::current().due_soon_days as u64);
+        let mut reminders = Vec::new();
+        for (list, items, active) in lists {
+            for item in items.iter().filter(|item| !item.is_done()) {
+                let Some(due) = item.due() else {
+                    continue;
+                };
+                let kind = match due {
+                    due if due < today => Kind::Overdue,
+                    due if due <= soon => Kind::DueSoon,
+                    _ => continue,
+                };
+                let task = (list.to_string(), item.id());
+                if self.snoozed.contains_key(&task) {
+                    continue;
+                }
+                if self.shown.insert((task, kind), due) == Some(due) {
+                    continue;
+                }
+                reminders.push(Reminder {
+                    kind,
+                    title: item.title().clone(),
+                    due,
+                    list: (!active).then(|| list.to_string()),
+                });
+            }
+        }
+
+        if reminders.len() <= MAX_SEPARATE {
+            return reminders
+                .iter()
+                .map(|reminder| reminder.notification(today))
+                .collect();
+        }
+        [Kind::Overdue, Kind::DueSoon]
+            .into_iter()
+            .filter_map(|kind| {
+                let count = reminders.iter().filter(|r| r.kind == kind).count();
+                let tasks = match count {
+                    1 => "1 task is".to_string(),
+                    count => format!("{} tasks are", count),
+                };
+                let message = match kind {
+                    Kind::Overdue => format!("{} overdue", tasks),
+                    Kind::DueSoon => format!("{} due soon", tasks),
+                };
+                (count > 0).then(|| kind.notification(message))
+            })
+            .collect()
+    }
+
+    // holds back the reminders of the tasks for the configured time,
+    // the reminders are shown again once it has passed
+    pub fn snooze(&mut self, list: &str, ids: &[u64]) {
+        let minutes = ReminderConfig::current().snooze_minutes;
+        let until = Instant::now() + Duration::from_secs(minutes * 60);
+        for id in ids {
+            let task = (list.to_string(), *id);
+            self.shown.retain(|(shown, _), _| *shown != task);
+            self.snoozed.insert(task, until);
+        }
+    }
+}
+
+impl Reminder {
+    fn notification(&self, today: NaiveDate) -> Notification {
+        let when = match (self.due - today).num_days() {
+            0 => "today".to_string(),
+            1 => "tomorrow".to_string(),
+            -1 => "yesterday".to_string(),
+            _ => format!("on {}", self.due.format(DATE_FORMAT)),
+        };
+        let message = match self.kind {
+            Kind::Overdue => format!("`{}` was due {}", self.title, when),
+            Kind::DueSoon => format!("`{}` is due {}", self.title, when),
+        };
+        let message = match &self.list {
+            Some(list) => format!("{} in `{}`", message, list),
+            None => message,
+        };
+        self.kind.notification(message)
+    }
+}
+
+impl Kind {
+    fn notification(self, message: String) -> Notification {
+        let (title, level) = match self {
+            Kind::Overdue => (" overdue ", NotificationLevel::Error),
+            Kind::DueSoon => (" due soon ", NotificationLevel::Warn),
+        };
+        Notification::new(title.into(), message, REMINDER_DURATION, level)
+    }
+}
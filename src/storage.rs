@@ -0,0 +1,135 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::widgets::{ListSettings, TodoItem, TodoList};
+
+// environment variable that overrides the directory
+// where the todo data is stored
+const DATA_DIR_ENV: &str = "TOODU_DATA_DIR";
+const DATA_FILE_EXTENSION: &str = "json";
+// the list opened by default, stored in `todo.json`
+pub const DEFAULT_LIST: &str = "todo";
+
+// the on disk representation of the application data, kept
+// as a struct so new fields can be added without breaking old files
+#[derive(Default, Serialize, Deserialize)]
+struct StoredData {
+    #[serde(default)]
+    items: Vec<TodoItem>,
+    #[serde(default)]
+    next_id: u64,
+    #[serde(default)]
+    settings: ListSettings,
+}
+
+// every list is stored in its own `<name>.json` file in the data directory
+pub struct Storage {
+    dir: PathBuf,
+}
+
+impl Storage {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    // creates a storage pointing to the default data directory,
+    // `$TOODU_DATA_DIR` if the variable is set, otherwise the
+    // platform data directory (`~/.local/share/toodu` on linux)
+    pub fn from_env() -> anyhow::Result<Self> {
+        let dir = match std::env::var_os(DATA_DIR_ENV) {
+            Some(dir) => PathBuf::from(dir),
+            None => dirs::data_dir()
+                .context("couldn't find the user data directory")?
+                .join("toodu"),
+        };
+        Ok(Self::new(dir))
+    }
+
+    // the names of the stored lists sorted by name, the
+    // default list is always included even if it wasn't saved yet
+    pub fn list_names(&self) -> anyhow::Result<Vec<String>> {
+        let mut names = vec![DEFAULT_LIST.to_string()];
+        if self.dir.exists() {
+            let entries = fs::read_dir(&self.dir).with_context(|| {
+                format!("couldn't read data directory `{}`", self.dir.display())
+            })?;
+            for entry in entries {
+                let path = entry?.path();
+                if path.extension().is_some_and(|e| e == DATA_FILE_EXTENSION) {
+                    if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    // loads the list with the given name from disk, a missing
+    // data file is not an error and results in an empty list
+    pub fn load(&self, name: &str) -> anyhow::Result<TodoList> {
+        let path = self.path(name);
+        if !path.exists() {
+            return Ok(TodoList::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("couldn't read data file `{}`", path.display()))?;
+        let data: StoredData = serde_json::from_str(&content)
+            .with_context(|| format!("couldn't parse data file `{}`", path.display()))?;
+        let mut todo_list = TodoList::new(data.items, data.next_id);
+        todo_list.set_settings(data.settings);
+        Ok(todo_list)
+    }
+
+    // writes the list with the given name to disk, the previous data file
+    // is copied to a backup file first and the new content is written to a
+    // temporary file that replaces the data file only after it was fully
+    // written, so a failed write never leaves us without the previous data
+    pub fn save(&self, name: &str, todo_list: &TodoList) -> anyhow::Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("couldn't create data directory `{}`", self.dir.display()))?;
+
+        let data = StoredData {
+            items: todo_list.items().to_vec(),
+            next_id: todo_list.next_id(),
+            settings: todo_list.settings().clone(),
+        };
+        let content =
+            serde_json::to_string_pretty(&data).context("couldn't serialize todo list")?;
+
+        let path = self.path(name);
+        if path.exists() {
+            fs::copy(&path, path.with_extension("json.bak"))
+                .with_context(|| format!("couldn't backup data file `{}`", path.display()))?;
+        }
+
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, content)
+            .with_context(|| format!("couldn't write data file `{}`", tmp_path.display()))?;
+        fs::rename(&tmp_path, &path)
+            .with_context(|| format!("couldn't replace data file `{}`", path.display()))?;
+        Ok(())
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", name, DATA_FILE_EXTENSION))
+    }
+}
+
+// checks the name can be used as a list file name
+pub fn validate_list_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("a list must have a name".into());
+    }
+    if name.trim() != name {
+        return Err("list names can't start or end with spaces".into());
+    }
+    if name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(format!("invalid list name `{}`", name));
+    }
+    Ok(())
+}
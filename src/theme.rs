@@ -0,0 +1,311 @@
+use anyhow::Context;
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::config;
+
+// the built in themes, the first one is the default
+const BUILTIN_THEMES: [&str; 3] = ["dark", "light", "high-contrast"];
+
+// the parts of the interface that can be styled, widgets
+// never use colors directly, they ask the theme for a role
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    // the background and text of the views
+    Base,
+    // every other row of the todo list
+    AltRow,
+    // the selected todo list row
+    Selected,
+    // the rows of the visual selection
+    Visual,
+    // the marker of the items marked for bulk operations
+    Marked,
+    // the group header rows of the todo list
+    GroupHeader,
+    // borders of unfocused inputs
+    Border,
+    // borders of focused inputs
+    FocusedBorder,
+    // border of the task content pane
+    PanelBorder,
+    // the input cursor
+    Cursor,
+    // ids, counts and field names
+    Accent,
+    // placeholder and help texts
+    Hint,
+    Due,
+    // the rows and due dates of unfinished tasks past their due date
+    Overdue,
+    Tag,
+    // the status of unfinished tasks, a configured status color replaces the color
+    Status,
+    // the status of finished tasks
+    StatusDone,
+    // chars matching the search filter, applied on top of the text style
+    Match,
+    PriorityLow,
+    PriorityMedium,
+    PriorityHigh,
+    Info,
+    Warn,
+    Error,
+    // the controls line at the bottom of the views
+    Controls,
+    ControlsSeparator,
+}
+
+// the styles of every role, roles without a style use the default style
+#[derive(Clone)]
+pub struct Theme {
+    styles: HashMap<Role, Style>,
+}
+
+// a style as written in a theme file
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct StyleConfig {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underlined: bool,
+    reversed: bool,
+}
+
+// a custom theme file, the styles replace the styles of the
+// built in theme it extends
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    #[serde(default = "default_extends")]
+    extends: String,
+    #[serde(default)]
+    styles: HashMap<Role, StyleConfig>,
+}
+
+impl Theme {
+    // the theme used by the application
+    pub fn current() -> &'static Theme {
+        &config::get().theme
+    }
+
+    pub fn style(&self, role: Role) -> Style {
+        self.styles.get(&role).copied().unwrap_or_default()
+    }
+
+    // returns the built in theme with the given name
+    pub fn builtin(name: &str) -> Option<Self> {
+        let styles = match name {
+            "dark" => dark_styles(),
+            "light" => light_styles(),
+            "high-contrast" => high_contrast_styles(),
+            _ => return None,
+        };
+        Some(Self {
+            styles: styles.into_iter().collect(),
+        })
+    }
+
+    // loads a custom theme file, plain names are looked up in the themes
+    // directory (`~/.config/toodu/themes` on linux), relative paths are
+    // relative to the config file and the `.toml` extension can be left out
+    pub fn load(name: &str) -> anyhow::Result<Self> {
+        let is_path = name.contains('/') || name.contains(std::path::MAIN_SEPARATOR);
+        let mut path = if Path::new(name).is_absolute() {
+            PathBuf::from(name)
+        } else if is_path {
+            match config::dir() {
+                Some(dir) => dir.join(name),
+                None => PathBuf::from(name),
+            }
+        } else {
+            match dirs::config_dir() {
+                Some(dir) => dir.join("toodu").join("themes").join(name),
+                None => PathBuf::from(name),
+            }
+        };
+        if path.extension().is_none() {
+            path.set_extension("toml");
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("couldn't read theme file `{}`", path.display()))?;
+        let file: ThemeFile = toml::from_str(&content)
+            .with_context(|| format!("couldn't parse theme file `{}`", path.display()))?;
+
+        let mut theme = Self::builtin(&file.extends).with_context(|| {
+            format!(
+                "theme `{}` extends unknown theme `{}`, expected one of {}",
+                path.display(),
+                file.extends,
+                BUILTIN_THEMES.join(", ")
+            )
+        })?;
+        for (role, style) in file.styles {
+            theme.styles.insert(role, style.into());
+        }
+        Ok(theme)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::builtin(BUILTIN_THEMES[0]).expect("the default theme is built in")
+    }
+}
+
+// the config names either a built in theme or a custom theme file
+impl<'de> Deserialize<'de> for Theme {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        match Self::builtin(&name) {
+            Some(theme) => Ok(theme),
+            None => Self::load(&name).map_err(|e| {
+                serde::de::Error::custom(format!(
+                    "`{}` is not a built in theme ({}) and {:#}",
+                    name,
+                    BUILTIN_THEMES.join(", "),
+                    e
+                ))
+            }),
+        }
+    }
+}
+
+impl From<StyleConfig> for Style {
+    fn from(config: StyleConfig) -> Self {
+        let modifiers = [
+            (config.bold, Modifier::BOLD),
+            (config.dim, Modifier::DIM),
+            (config.italic, Modifier::ITALIC),
+            (config.underlined, Modifier::UNDERLINED),
+            (config.reversed, Modifier::REVERSED),
+        ];
+
+        let mut style = Style::default();
+        if let Some(fg) = config.fg {
+            style = style.fg(fg);
+        }
+        if let Some(bg) = config.bg {
+            style = style.bg(bg);
+        }
+        for (enabled, modifier) in modifiers {
+            if enabled {
+                style = style.add_modifier(modifier);
+            }
+        }
+        style
+    }
+}
+
+fn default_extends() -> String {
+    BUILTIN_THEMES[0].into()
+}
+
+fn dark_styles() -> Vec<(Role, Style)> {
+    let style = Style::default;
+    vec![
+        (Role::Base, style().white().on_black()),
+        (Role::AltRow, style().on_dark_gray()),
+        (Role::Selected, style().black().on_white().bold()),
+        (Role::Visual, style().black().on_light_blue()),
+        (Role::Marked, style().light_green().bold()),
+        (Role::GroupHeader, style().on_blue()),
+        (Role::Border, style().white()),
+        (Role::FocusedBorder, style().light_blue()),
+        (Role::PanelBorder, style().light_blue()),
+        (Role::Cursor, style().reversed()),
+        (Role::Accent, style().light_cyan()),
+        (Role::Hint, style().cyan()),
+        (Role::Due, style().light_magenta()),
+        (Role::Overdue, style().light_red()),
+        (Role::Tag, style().cyan()),
+        (Role::Status, style().light_blue()),
+        (Role::StatusDone, style().light_green()),
+        (Role::Match, style().light_yellow().bold().underlined()),
+        (Role::PriorityLow, style().gray()),
+        (Role::PriorityMedium, style().light_yellow()),
+        (Role::PriorityHigh, style().light_red().bold()),
+        (Role::Info, style().light_blue()),
+        (Role::Warn, style().light_yellow()),
+        (Role::Error, style().light_red()),
+        (Role::Controls, style().black().on_white().bold()),
+        (Role::ControlsSeparator, style().black()),
+    ]
+}
+
+fn light_styles() -> Vec<(Role, Style)> {
+    let style = Style::default;
+    vec![
+        (Role::Base, style().black().on_white()),
+        (Role::AltRow, style().on_gray()),
+        (Role::Selected, style().white().on_blue().bold()),
+        (Role::Visual, style().black().on_light_cyan()),
+        (Role::Marked, style().green().bold()),
+        (Role::GroupHeader, style().black().on_light_blue()),
+        (Role::Border, style().dark_gray()),
+        (Role::FocusedBorder, style().blue()),
+        (Role::PanelBorder, style().blue()),
+        (Role::Cursor, style().reversed()),
+        (Role::Accent, style().blue()),
+        (Role::Hint, style().dark_gray()),
+        (Role::Due, style().magenta()),
+        (Role::Overdue, style().red()),
+        (Role::Tag, style().cyan()),
+        (Role::Status, style().blue()),
+        (Role::StatusDone, style().green()),
+        (Role::Match, style().red().bold().underlined()),
+        (Role::PriorityLow, style().dark_gray()),
+        (Role::PriorityMedium, style().magenta()),
+        (Role::PriorityHigh, style().red().bold()),
+        (Role::Info, style().blue()),
+        (Role::Warn, style().magenta()),
+        (Role::Error, style().red()),
+        (Role::Controls, style().white().on_black().bold()),
+        (Role::ControlsSeparator, style().gray()),
+    ]
+}
+
+fn high_contrast_styles() -> Vec<(Role, Style)> {
+    let style = Style::default;
+    vec![
+        (Role::Base, style().white().on_black()),
+        (Role::AltRow, style()),
+        (Role::Selected, style().black().on_yellow().bold()),
+        (Role::Visual, style().black().on_cyan()),
+        (Role::Marked, style().light_green().bold()),
+        (Role::GroupHeader, style().black().on_white().bold()),
+        (Role::Border, style().white()),
+        (Role::FocusedBorder, style().yellow().bold()),
+        (Role::PanelBorder, style().white()),
+        (Role::Cursor, style().black().on_yellow()),
+        (Role::Accent, style().yellow()),
+        (Role::Hint, style().white().italic()),
+        (Role::Due, style().light_cyan().bold()),
+        (Role::Overdue, style().light_red().bold().underlined()),
+        (Role::Tag, style().light_green()),
+        (Role::Status, style().light_cyan()),
+        (Role::StatusDone, style().light_green().bold()),
+        (Role::Match, style().black().on_light_cyan().bold()),
+        (Role::PriorityLow, style().white()),
+        (Role::PriorityMedium, style().yellow().bold()),
+        (Role::PriorityHigh, style().light_red().bold().underlined()),
+        (Role::Info, style().white().bold()),
+        (Role::Warn, style().yellow().bold()),
+        (Role::Error, style().light_red().bold()),
+        (Role::Controls, style().black().on_white().bold()),
+        (Role::ControlsSeparator, style().black()),
+    ]
+}
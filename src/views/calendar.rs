@@ -0,0 +1,274 @@
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, Padding},
+};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use super::{controls_line, View};
+use crate::{
+    app::ApplicationState,
+    keymap::{Action, Context, Keymap},
+    theme::{Role, Theme},
+    widgets::{CenteredText, Priority, TodoItem, DATE_FORMAT},
+    workflow::Workflow,
+};
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+// a month calendar of the active list with the count of unfinished
+// tasks due on every day, the agenda next to it lists the tasks
+// due on the selected day
+pub struct CalendarView {
+    app_state: Rc<RefCell<ApplicationState>>,
+    selected: NaiveDate,
+    // where the days of the month were last rendered
+    day_areas: Vec<(Rect, NaiveDate)>,
+}
+
+impl CalendarView {
+    pub fn new(app_state: Rc<RefCell<ApplicationState>>) -> Self {
+        Self {
+            app_state,
+            selected: Local::now().date_naive(),
+            day_areas: Vec::new(),
+        }
+    }
+
+    // moves the selected day, days past the ends of
+    // the supported dates are ignored
+    fn move_days(&mut self, days: i64) {
+        let moved = if days < 0 {
+            self.selected
+                .checked_sub_days(Days::new(days.unsigned_abs()))
+        } else {
+            self.selected.checked_add_days(Days::new(days as u64))
+        };
+        if let Some(date) = moved {
+            self.selected = date;
+        }
+    }
+
+    // moves the selected day by months, the day is kept
+    // or moved to the last day of shorter months
+    fn move_months(&mut self, forward: bool) {
+        let moved = if forward {
+            self.selected.checked_add_months(Months::new(1))
+        } else {
+            self.selected.checked_sub_months(Months::new(1))
+        };
+        if let Some(date) = moved {
+            self.selected = date;
+        }
+    }
+
+    // the number of unfinished tasks due on every day of the selected month
+    fn month_counts(&self) -> HashMap<NaiveDate, usize> {
+        let mut counts = HashMap::new();
+        let state = self.app_state.borrow();
+        for item in state.todo_list.items() {
+            let Some(due) = item.due().filter(|_| !item.is_done()) else {
+                continue;
+            };
+            if due.year() == self.selected.year() && due.month() == self.selected.month() {
+                *counts.entry(due).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    fn render_title(&self, area: Rect, buf: &mut Buffer) {
+        let state = self.app_state.borrow();
+        let theme = Theme::current();
+        Line::from(vec![
+            Span::from(format!(" {} ", state.list_name)).bold(),
+            Span::styled(
+                self.selected.format("%B %Y").to_string(),
+                theme.style(Role::Accent),
+            ),
+        ])
+        .style(theme.style(Role::Base))
+        .render(area, buf);
+    }
+
+    fn render_month(&mut self, area: Rect, buf: &mut Buffer) {
+        let theme = Theme::current();
+        let block = Block::default()
+            .borders(Borders::RIGHT)
+            .border_style(theme.style(Role::PanelBorder))
+            .padding(Padding::horizontal(1))
+            .style(theme.style(Role::Base));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let first = self.selected.with_day(1).unwrap_or(self.selected);
+        let offset = first.weekday().num_days_from_monday();
+        let days_in_month = first
+            .checked_add_months(Months::new(1))
+            .map(|next| next.signed_duration_since(first).num_days() as u32)
+            .unwrap_or(31);
+        let weeks = (offset + days_in_month).div_ceil(7);
+
+        // the weeks get two rows when there is room, so the days are easier to tell apart
+        let row_height = if inner.height > weeks as u16 * 2 {
+            2
+        } else {
+            1
+        };
+        let mut rows = vec![Constraint::Length(1)];
+        rows.extend((0..weeks).map(|_| Constraint::Length(row_height)));
+        let rows = Layout::vertical(rows).split(inner);
+        let columns = |row: Rect| Layout::horizontal([Constraint::Ratio(1, 7); 7]).split(row);
+
+        for (name, cell) in WEEKDAYS.iter().zip(columns(rows[0]).iter()) {
+            Line::styled(*name, theme.style(Role::Hint)).render(*cell, buf);
+        }
+
+        let counts = self.month_counts();
+        let today = Local::now().date_naive();
+        self.day_areas.clear();
+        for (i, date) in first.iter_days().take(days_in_month as usize).enumerate() {
+            let position = offset as usize + i;
+            let cell = columns(rows[1 + position / 7])[position % 7];
+
+            let mut day_style = Style::default();
+            if date == today {
+                day_style = day_style.patch(theme.style(Role::Accent)).bold();
+            }
+            let mut spans = vec![Span::styled(format!("{:>2}", date.day()), day_style)];
+            if let Some(count) = counts.get(&date) {
+                let role = if date < today {
+                    Role::Overdue
+                } else {
+                    Role::Due
+                };
+                spans.push(Span::styled(format!(" {}", count), theme.style(role)));
+            }
+
+            let mut line = Line::from(spans);
+            if date == self.selected {
+                line = line.style(theme.style(Role::Selected));
+            }
+            line.render(cell, buf);
+            self.day_areas.push((cell, date));
+        }
+    }
+
+    fn render_agenda(&self, area: Rect, buf: &mut Buffer) {
+        let theme = Theme::current();
+        let state = self.app_state.borrow();
+        let tasks: Vec<&TodoItem> = state
+            .todo_list
+            .items()
+            .iter()
+            .filter(|item| item.due() == Some(self.selected))
+            .collect();
+
+        let block = Block::default()
+            .title(Line::from(vec![
+                Span::from(format!(" {} ", self.selected.format("%a"))).bold(),
+                Span::from(self.selected.format(DATE_FORMAT).to_string()),
+                Span::styled(format!(" ({}) ", tasks.len()), theme.style(Role::Accent)),
+            ]))
+            .padding(Padding::horizontal(1))
+            .style(theme.style(Role::Base));
+        if tasks.is_empty() {
+            CenteredText::new(
+                Text::from("no tasks due on this day").style(theme.style(Role::Hint)),
+            )
+            .block(block)
+            .render(area, buf);
+            return;
+        }
+
+        let workflow = Workflow::current();
+        let status_width = workflow.name_width();
+        let items = tasks.into_iter().map(|item| {
+            let status = item.status().name();
+            let status_style = workflow.style(status);
+
+            let mut spans = vec![
+                Span::styled(
+                    format!("{:<width$}", status, width = status_width),
+                    status_style,
+                ),
+                Span::styled(format!(" (#{}) ", item.id()), theme.style(Role::Accent)),
+                Span::from(item.title().clone()),
+            ];
+            let priority_role = match item.priority() {
+                Priority::None => None,
+                Priority::Low => Some(Role::PriorityLow),
+                Priority::Medium => Some(Role::PriorityMedium),
+                Priority::High => Some(Role::PriorityHigh),
+            };
+            if let Some(role) = priority_role {
+                spans.push(Span::styled(
+                    format!(" !{}", item.priority()),
+                    theme.style(role),
+                ));
+            }
+
+            let style = if item.is_done() {
+                theme.style(Role::Hint)
+            } else if item.is_overdue() {
+                theme.style(Role::Overdue)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(spans)).style(style)
+        });
+        Widget::render(List::new(items).block(block), area, buf);
+    }
+}
+
+impl View for CalendarView {
+    fn view_event_key(&mut self, key: KeyEvent) {
+        match Keymap::current().action(Context::Calendar, &key) {
+            Some(Action::PrevDay) => self.move_days(-1),
+            Some(Action::NextDay) => self.move_days(1),
+            Some(Action::Up) => self.move_days(-7),
+            Some(Action::Down) => self.move_days(7),
+            Some(Action::PrevMonth) => self.move_months(false),
+            Some(Action::NextMonth) => self.move_months(true),
+            Some(Action::Today) => self.selected = Local::now().date_naive(),
+            _ => {}
+        }
+    }
+
+    // clicking a day selects it, the wheel moves a week at a time
+    fn view_event_mouse(&mut self, mouse: MouseEvent) {
+        let position = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.move_days(7),
+            MouseEventKind::ScrollUp => self.move_days(-7),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let clicked = self
+                    .day_areas
+                    .iter()
+                    .find(|(area, _)| area.contains(position));
+                if let Some((_, date)) = clicked {
+                    self.selected = *date;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn render_view(&mut self, area: Rect, buf: &mut Buffer) {
+        let [title_area, main_area, controls_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        let [month_area, agenda_area] =
+            Layout::horizontal([Constraint::Length(52), Constraint::Fill(1)]).areas(main_area);
+
+        self.render_title(title_area, buf);
+        self.render_month(month_area, buf);
+        self.render_agenda(agenda_area, buf);
+        controls_line(&[Context::Calendar, Context::Global], controls_area.width)
+            .render(controls_area, buf);
+    }
+}
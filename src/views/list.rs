@@ -1,47 +1,371 @@
 use ratatui::{
-    crossterm::event::KeyCode,
+    crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
     prelude::*,
     widgets::{Block, Borders, Padding, Paragraph},
 };
 use std::{cell::RefCell, rc::Rc, time::Duration};
 
-use super::View;
+use super::{controls_line, View, ViewKind};
 use crate::{
-    app::ApplicationState,
-    widgets::{CenteredText, Notification, NotificationLevel},
+    app::{ApplicationMode, ApplicationRunningState, ApplicationState},
+    commands::{self, Command},
+    formats,
+    keymap::{plain_char, Action, Context, Keymap},
+    reminders::ReminderConfig,
+    theme::{Role, Theme},
+    widgets::{
+        CenteredText, CommandLine, Input, ListClick, ListSwitcher, Notification, NotificationLevel,
+        SwitcherPurpose, TodoItem, DATE_FORMAT, TIMESTAMP_FORMAT,
+    },
+    workflow::Workflow,
 };
 
+// the largest count that can be typed before an action
+const MAX_COUNT: usize = 9999;
+
 pub struct ListView {
     app_state: Rc<RefCell<ApplicationState>>,
+    search: Input,
+    // true while the search input is focused
+    searching: bool,
+    // the list switcher popup, shown while choosing a list
+    switcher: Option<ListSwitcher>,
+    // where the search input was last rendered, `None` while hidden
+    search_area: Option<Rect>,
+    // the `:` command line, shown while a command is entered
+    command_line: Option<CommandLine>,
+    // the count typed before an action, like the 5 of `5j`
+    count: Option<usize>,
+    // a doubled action waiting for the second press of its key
+    pending: Option<(Action, char)>,
 }
 
 impl ListView {
     pub fn new(app_state: Rc<RefCell<ApplicationState>>) -> Self {
-        Self { app_state }
+        let mut search = Input::new("search");
+        search.set_value(app_state.borrow().todo_list.filter().to_string());
+        app_state.borrow_mut().todo_list.stop_visual();
+
+        Self {
+            app_state,
+            search,
+            searching: false,
+            switcher: None,
+            search_area: None,
+            command_line: None,
+            count: None,
+            pending: None,
+        }
+    }
+
+    fn open_command_line(&mut self, text: &str) {
+        self.command_line = Some(CommandLine::new(text));
+        self.app_state.borrow_mut().mode = ApplicationMode::Command;
+    }
+
+    fn close_command_line(&mut self) {
+        self.command_line = None;
+        self.app_state.borrow_mut().mode = ApplicationMode::Normal;
+    }
+
+    fn command_event_key(&mut self, key: KeyEvent) {
+        let Some(command_line) = &mut self.command_line else {
+            return;
+        };
+
+        match Keymap::current().action(Context::Command, &key) {
+            Some(Action::Complete) if command_line.completing() => command_line.next_completion(),
+            Some(Action::Complete) => {
+                let (start, candidates) =
+                    commands::complete(command_line.value(), &self.app_state.borrow());
+                command_line.start_completion(start, candidates);
+            }
+            Some(Action::Submit) => {
+                let input = command_line.value().trim().to_string();
+                self.close_command_line();
+                if input.is_empty() {
+                    return;
+                }
+                match input.parse() {
+                    Ok(command) => self.run_command(command),
+                    Err(message) => self.notify_error(" command ", message),
+                }
+            }
+            Some(Action::Cancel) => self.close_command_line(),
+            // like in vim, erasing past the start of the line leaves it
+            _ if key.code == KeyCode::Backspace && command_line.value().is_empty() => {
+                self.close_command_line();
+            }
+            _ => {
+                command_line.handle_key(key);
+            }
+        }
+    }
+
+    fn run_command(&mut self, command: Command) {
+        let mut state = self.app_state.borrow_mut();
+        match command {
+            Command::Write | Command::WriteQuit => {
+                // the changed lists are written after every event
+                for (_, todo_list) in state.all_lists_mut() {
+                    todo_list.mark_changed();
+                }
+                if matches!(command, Command::WriteQuit) {
+                    state.running_state = ApplicationRunningState::Exiting;
+                }
+            }
+            Command::Quit => state.running_state = ApplicationRunningState::Exiting,
+            Command::Sort(sort) => state.todo_list.set_sort(sort),
+            Command::Group(group) => state.todo_list.set_group(group),
+            Command::Filter(filter) => {
+                state.todo_list.set_filter(&filter);
+                self.search.set_value(filter);
+            }
+            Command::List(name) => {
+                if state.switch_list(&name) {
+                    state.requested_view = Some(ViewKind::List);
+                } else {
+                    drop(state);
+                    self.notify_error(" command ", format!("no list named `{}`", name));
+                }
+            }
+            Command::Row(row) => state.todo_list.select_row(row.saturating_sub(1)),
+            Command::Tag { add, remove } => state.edit_list(|list| {
+                let ids = list.target_ids(1);
+                list.retag(&ids, &add, &remove);
+            }),
+            Command::Export(path) => {
+                let result = formats::export_file(&path, None, state.todo_list.items());
+                drop(state);
+                match result {
+                    Ok(()) => {
+                        self.notify_info(" export ", format!("exported to {}", path.display()))
+                    }
+                    Err(e) => self.notify_error(" export failed ", format!("{:#}", e)),
+                }
+            }
+            Command::Import(path) => match formats::import_file(&path, None) {
+                Ok(items) => {
+                    let count = state.edit_list(|list| list.add_items(items).len());
+                    drop(state);
+                    self.notify_info(" import ", format!("imported {} tasks", count));
+                }
+                Err(e) => {
+                    drop(state);
+                    self.notify_error(" import failed ", format!("{:#}", e));
+                }
+            },
+        }
+    }
+
+    fn start_visual(&mut self) {
+        let mut state = self.app_state.borrow_mut();
+        if state.todo_list.start_visual() {
+            state.mode = ApplicationMode::Visual;
+        }
+    }
+
+    fn stop_visual(state: &mut ApplicationState) {
+        state.todo_list.stop_visual();
+        if state.mode == ApplicationMode::Visual {
+            state.mode = ApplicationMode::Normal;
+        }
+    }
+
+    // the count and the first key of a doubled action, shown
+    // while the rest of the keys is typed
+    fn pending_keys(&self) -> String {
+        let count = self.count.map(|c| c.to_string()).unwrap_or_default();
+        match self.pending {
+            Some((_, c)) => format!("{}{}", count, c),
+            None => count,
+        }
+    }
+
+    fn open_switcher(&mut self, purpose: SwitcherPurpose) {
+        let mut state = self.app_state.borrow_mut();
+        let switcher = ListSwitcher::new(purpose, state.list_counts(), &state.list_name);
+        self.switcher = Some(switcher);
+        state.mode = ApplicationMode::Writing;
+    }
+
+    fn close_switcher(&mut self) {
+        self.switcher = None;
+        self.app_state.borrow_mut().mode = ApplicationMode::Normal;
+    }
+
+    fn switcher_event_key(&mut self, key: KeyEvent) {
+        let Some(switcher) = &mut self.switcher else {
+            return;
+        };
+        let action = Keymap::current().action(Context::Switcher, &key);
+
+        if switcher.creating() {
+            match action {
+                Some(Action::Submit) => {
+                    let name = switcher.new_list_name().to_string();
+                    let created = self.app_state.borrow_mut().create_list(&name);
+                    match created {
+                        Ok(()) => self.select_list(&name),
+                        Err(message) => self.notify_error(" invalid list ", message),
+                    }
+                }
+                Some(Action::Cancel) => switcher.set_creating(false),
+                _ => {
+                    switcher.input_mut().handle_key(key);
+                }
+            }
+            return;
+        }
+
+        match action {
+            Some(Action::Up) => switcher.prev(),
+            Some(Action::Down) => switcher.next(),
+            Some(Action::NewList) => switcher.set_creating(true),
+            Some(Action::Submit) => {
+                if let Some(name) = switcher.selected().map(String::from) {
+                    self.select_list(&name);
+                }
+            }
+            Some(Action::Cancel) => self.close_switcher(),
+            _ => {}
+        }
+    }
+
+    // opens the list or moves the selected task to it,
+    // depending on why the switcher was opened
+    fn select_list(&mut self, name: &str) {
+        let Some(purpose) = self.switcher.as_ref().map(ListSwitcher::purpose) else {
+            return;
+        };
+        self.close_switcher();
+
+        let mut state = self.app_state.borrow_mut();
+        match purpose {
+            SwitcherPurpose::Open => {
+                state.switch_list(name);
+                // the view is recreated so the search shows the list filter
+                state.requested_view = Some(ViewKind::List);
+            }
+            SwitcherPurpose::Move => {
+                let ids = state.todo_list.target_ids(1);
+                match state.move_items_to(name, &ids) {
+                    Ok(titles) => {
+                        let message = match titles.as_slice() {
+                            [title] => format!("moved task `{}` to `{}`", title, name),
+                            titles => format!("moved {} tasks to `{}`", titles.len(), name),
+                        };
+                        state.notifications.push_notification(Notification::new(
+                            " moved task ".into(),
+                            message,
+                            Duration::from_secs(3),
+                            NotificationLevel::Info,
+                        ));
+                    }
+                    Err(message) => {
+                        drop(state);
+                        self.notify_error(" move failed ", message);
+                    }
+                }
+            }
+        }
+    }
+
+    fn notify_info(&self, title: &str, message: String) {
+        self.app_state
+            .borrow_mut()
+            .notifications
+            .push_notification(Notification::new(
+                title.into(),
+                message,
+                Duration::from_secs(3),
+                NotificationLevel::Info,
+            ));
+    }
+
+    fn notify_error(&self, title: &str, message: String) {
+        self.app_state
+            .borrow_mut()
+            .notifications
+            .push_notification(Notification::new(
+                title.into(),
+                message,
+                Duration::from_secs(3),
+                NotificationLevel::Error,
+            ));
+    }
+
+    fn start_search(&mut self) {
+        self.searching = true;
+        self.search.set_focused(true);
+        self.app_state.borrow_mut().mode = ApplicationMode::Writing;
+    }
+
+    // leaves the search input, the filter is kept unless `clear` is set
+    fn stop_search(&mut self, clear: bool) {
+        let mut state = self.app_state.borrow_mut();
+        if clear {
+            self.search.clear();
+            state.todo_list.set_filter("");
+        }
+
+        self.searching = false;
+        self.search.set_focused(false);
+        state.mode = ApplicationMode::Normal;
+    }
+
+    // the list is filtered as the search input changes, the selection
+    // can still be moved with the arrow keys while typing
+    fn search_event_key(&mut self, key: KeyEvent) {
+        match Keymap::current().action(Context::Search, &key) {
+            Some(Action::Submit) => self.stop_search(false),
+            Some(Action::Cancel) => self.stop_search(true),
+            Some(Action::Down) => self.app_state.borrow_mut().todo_list.next(),
+            Some(Action::Up) => self.app_state.borrow_mut().todo_list.prev(),
+            _ => {
+                if self.search.handle_key(key) {
+                    self.app_state
+                        .borrow_mut()
+                        .todo_list
+                        .set_filter(self.search.value());
+                }
+            }
+        }
     }
 
     fn render_todo_item_content(&self, area: Rect, buf: &mut Buffer) {
-        let block = Block::default().borders(Borders::TOP).white().on_black();
+        let theme = Theme::current();
+        let block = Block::default()
+            .borders(Borders::TOP)
+            .style(theme.style(Role::Base));
         match self.app_state.borrow().todo_list.selected() {
             Some(item) => {
                 let inner_block = Block::bordered()
                     .padding(Padding::horizontal(1))
-                    .border_style(Style::default().light_blue())
-                    .on_black();
+                    .border_style(theme.style(Role::PanelBorder));
                 let inner_area = Layout::default()
                     .vertical_margin(1)
                     .horizontal_margin(5)
                     .constraints([Constraint::Min(0)])
                     .split(block.inner(area))[0];
 
+                let mut lines = ListView::item_metadata_lines(item);
+                lines.push(Line::default());
+                lines.extend(
+                    item.description()
+                        .lines()
+                        .map(|l| Line::from(l.to_string())),
+                );
+
                 block.render(area, buf);
-                Paragraph::new(item.description().clone())
+                Paragraph::new(lines)
                     .block(inner_block)
                     .render(inner_area, buf);
             }
             None => {
                 CenteredText::new(
-                    Text::from("scroll on some tasks to view thier content here").cyan(),
+                    Text::from("scroll on some tasks to view thier content here")
+                        .style(theme.style(Role::Hint)),
                 )
                 .block(block)
                 .render(area, buf);
@@ -49,52 +373,391 @@ impl ListView {
         }
     }
 
-    fn render_controls_line(&self, area: Rect, buf: &mut Buffer) {
-        Line::from(key_spans!(
-            "k/j",
-            "UP/DN",
-            "TAB/Enter",
-            "toggle task status",
-            "n",
-            "new task",
-            "d/DEL",
-            "delete task",
-            "q/esc",
-            "quit"
-        ))
-        .black()
-        .on_white()
-        .bold()
+    // the item fields shown above the description in the content pane
+    fn item_metadata_lines(item: &TodoItem) -> Vec<Line<'static>> {
+        let accent = Theme::current().style(Role::Accent);
+        let styled_field = |name: &str, value: String, style: Style| {
+            vec![
+                Span::styled(format!("{}: ", name), accent),
+                Span::styled(value, style),
+                Span::from("   "),
+            ]
+        };
+        let field = |name: &str, value: String| styled_field(name, value, Style::default());
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "-".into());
+
+        let tags = item
+            .tags()
+            .iter()
+            .map(|tag| format!("#{}", tag))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let details = [
+            styled_field(
+                "status",
+                item.status().to_string(),
+                Workflow::current().style(item.status().name()),
+            ),
+            field("priority", item.priority().to_string()),
+            field(
+                "due",
+                optional(item.due().map(|d| d.format(DATE_FORMAT).to_string())),
+            ),
+            field("tags", if tags.is_empty() { "-".into() } else { tags }),
+            field("repeat", optional(item.repeat().map(|r| r.to_string()))),
+        ];
+        let timestamps = [
+            field(
+                "created",
+                item.created().format(TIMESTAMP_FORMAT).to_string(),
+            ),
+            field(
+                "updated",
+                item.updated().format(TIMESTAMP_FORMAT).to_string(),
+            ),
+            field(
+                "completed",
+                optional(
+                    item.completed()
+                        .map(|d| d.format(TIMESTAMP_FORMAT).to_string()),
+                ),
+            ),
+        ];
+
+        vec![
+            Line::from(details.concat()),
+            Line::from(timestamps.concat()),
+        ]
+    }
+
+    // undoes or redoes the last `count` changes to the todo list,
+    // the user is notified when there is nothing to undo
+    fn undo(state: &mut ApplicationState, redo: bool, count: usize) {
+        let action = if redo { "redo" } else { "undo" };
+        let applied = (0..count)
+            .take_while(|_| if redo { state.redo() } else { state.undo() })
+            .count();
+
+        if applied == 0 {
+            state.notifications.push_notification(Notification::new(
+                format!(" {} ", action),
+                format!("nothing to {}", action),
+                Duration::from_secs(2),
+                NotificationLevel::Info,
+            ));
+        }
+    }
+
+    // the name and item count of the active list, the mode
+    // and the keys typed so far are shown on the right
+    fn render_list_title(&self, area: Rect, buf: &mut Buffer) {
+        let state = self.app_state.borrow();
+        let theme = Theme::current();
+        Line::from(vec![
+            Span::from(format!(" {} ", state.list_name)).bold(),
+            Span::styled(
+                format!("({})", state.todo_list.items().len()),
+                theme.style(Role::Accent),
+            ),
+        ])
+        .style(theme.style(Role::Base))
         .render(area, buf);
+
+        let mut status = self.pending_keys();
+        let marked = state.todo_list.marked_count();
+        if marked > 0 {
+            status.push_str(&format!(" {} marked", marked));
+        }
+        if state.mode == ApplicationMode::Visual {
+            status.push_str(" -- VISUAL --");
+        }
+        let [_, status_area] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(status.len() as u16 + 1),
+        ])
+        .areas(area);
+        Line::styled(status, theme.style(Role::Hint)).render(status_area, buf);
+    }
+
+    fn render_controls_line(&self, area: Rect, buf: &mut Buffer) {
+        if let Some(command_line) = &self.command_line {
+            command_line.render(area, buf);
+            return;
+        }
+
+        let line = if self.switcher.is_some() {
+            controls_line(&[Context::Switcher], area.width)
+        } else if self.app_state.borrow().mode == ApplicationMode::Visual {
+            controls_line(&[Context::Visual, Context::List], area.width)
+        } else if self.searching {
+            controls_line(&[Context::Search], area.width)
+        } else {
+            controls_line(&[Context::List, Context::Global], area.width)
+        };
+        line.render(area, buf);
     }
 }
 
 impl View for ListView {
-    fn view_event_key(&mut self, key: KeyCode) {
-        let mut state = self.app_state.borrow_mut();
+    fn view_event_key(&mut self, key: KeyEvent) {
+        if self.switcher.is_some() {
+            self.switcher_event_key(key);
+            return;
+        }
+        if self.searching {
+            self.search_event_key(key);
+            return;
+        }
+        if self.command_line.is_some() {
+            self.command_event_key(key);
+            return;
+        }
+
+        let keymap = Keymap::current();
+        let visual = self.app_state.borrow().mode == ApplicationMode::Visual;
+        if visual && keymap.action(Context::Visual, &key) == Some(Action::Cancel) {
+            return ListView::stop_visual(&mut self.app_state.borrow_mut());
+        }
+        let action = keymap.action(Context::List, &key);
+
+        // digits that are not bound to an action make up the count,
+        // a count can't start with 0
+        if let (None, Some(digit)) = (action, plain_char(&key).and_then(|c| c.to_digit(10))) {
+            if digit > 0 || self.count.is_some() {
+                let count = self.count.unwrap_or(0) * 10 + digit as usize;
+                self.count = Some(count.min(MAX_COUNT));
+                self.pending = None;
+                return;
+            }
+        }
+
+        // a doubled action runs on the second press of its key, any other
+        // key cancels it, in visual mode `d` deletes right away like in vim
+        let pending = self.pending.take();
+        if let (Some(action), Some(c)) = (action, plain_char(&key)) {
+            let immediate = visual && action == Action::Delete;
+            if action.doubled() && !immediate && pending != Some((action, c)) {
+                self.pending = Some((action, c));
+                return;
+            }
+        }
+        let count = self.count.take();
+
+        match action {
+            Some(Action::Search) if !visual => return self.start_search(),
+            Some(Action::OpenList) if !visual => return self.open_switcher(SwitcherPurpose::Open),
+            Some(Action::MoveToList) if !visual => {
+                return self.open_switcher(SwitcherPurpose::Move)
+            }
+            Some(Action::Visual) if !visual => return self.start_visual(),
+            Some(Action::CommandLine) if !visual => return self.open_command_line(""),
+            Some(Action::Tag) if !visual => return self.open_command_line("tag "),
+            _ => {}
+        }
+
+        // only the motions and the actions that work on a range of
+        // rows are used while selecting the range
+        let range_action = matches!(
+            action,
+            Some(
+                Action::Up
+                    | Action::Down
+                    | Action::Top
+                    | Action::Bottom
+                    | Action::Delete
+                    | Action::NextStatus
+                    | Action::PrevStatus
+                    | Action::ToggleMark
+                    | Action::Snooze
+            )
+        );
+        if visual && !range_action {
+            return;
+        }
 
-        match key {
-            KeyCode::Char('j') | KeyCode::Down => state.todo_list.next(),
-            KeyCode::Char('k') | KeyCode::Up => state.todo_list.prev(),
-            KeyCode::Char('d') | KeyCode::Delete => {
-                if let Some(item) = state.todo_list.delete_current() {
+        let n = count.unwrap_or(1);
+        let mut state = self.app_state.borrow_mut();
+        match action {
+            Some(Action::Down) => state.todo_list.select_relative(n as isize),
+            Some(Action::Up) => state.todo_list.select_relative(-(n as isize)),
+            // with a count both go to the row with that number like `5G`
+            Some(Action::Top) => state.todo_list.select_row(count.unwrap_or(1) - 1),
+            Some(Action::Bottom) => state.todo_list.select_row(count.unwrap_or(usize::MAX) - 1),
+            Some(Action::Delete) => {
+                let removed = state.edit_list(|list| {
+                    let ids = list.target_ids(n);
+                    list.remove_items(&ids)
+                });
+                ListView::stop_visual(&mut state);
+                let mut message = match removed.as_slice() {
+                    [] => return,
+                    [item] => format!(
+                        "deleted item `{}` from todo list with status {}",
+                        item.title(),
+                        item.status()
+                    ),
+                    items => format!("deleted {} items from todo list", items.len()),
+                };
+                if let Some(keys) = keymap.keys(Context::List, Action::Undo) {
+                    message.push_str(&format!(", press {} to undo", keys));
+                }
+                state.notifications.push_notification(Notification::new(
+                    " deleted item ".into(),
+                    message,
+                    Duration::from_secs(5),
+                    NotificationLevel::Warn,
+                ));
+            }
+            Some(Action::NextStatus) if !visual && state.todo_list.selected_group().is_some() => {
+                state.todo_list.toggle_current_group();
+            }
+            Some(Action::NextStatus | Action::PrevStatus) => {
+                let forward = action == Some(Action::NextStatus);
+                state.edit_list(|list| {
+                    let ids = list.target_ids(n);
+                    list.cycle_status_of(&ids, forward);
+                });
+                ListView::stop_visual(&mut state);
+            }
+            Some(Action::Sort) => {
+                let sort = state.todo_list.cycle_sort();
+                state.notifications.push_notification(Notification::new(
+                    " sort ".into(),
+                    format!("sorting tasks by {}", sort),
+                    Duration::from_secs(2),
+                    NotificationLevel::Info,
+                ));
+            }
+            Some(Action::Group) => {
+                let group = state.todo_list.cycle_group();
+                state.notifications.push_notification(Notification::new(
+                    " group ".into(),
+                    format!("grouping tasks by {}", group),
+                    Duration::from_secs(2),
+                    NotificationLevel::Info,
+                ));
+            }
+            Some(Action::Edit) => {
+                if let Some(item) = state.todo_list.selected() {
+                    state.requested_view = Some(ViewKind::EditTask(item.id()));
+                }
+            }
+            Some(Action::AddSubtask) => {
+                if let Some(item) = state.todo_list.selected() {
+                    state.requested_view = Some(ViewKind::NewSubtask(item.id()));
+                }
+            }
+            Some(Action::Fold) => state.todo_list.fold_current(),
+            Some(Action::Unfold) => state.todo_list.unfold_current(),
+            // marking moves to the next row so rows can be marked one after another
+            Some(Action::ToggleMark) => {
+                state.todo_list.toggle_marks(n);
+                if visual {
+                    ListView::stop_visual(&mut state);
+                } else {
+                    state.todo_list.select_relative(n as isize);
+                }
+            }
+            Some(Action::MoveDown | Action::MoveUp | Action::MoveToTop | Action::MoveToBottom) => {
+                let steps = match action {
+                    Some(Action::MoveDown) => n as isize,
+                    Some(Action::MoveUp) => -(n as isize),
+                    Some(Action::MoveToTop) => -isize::MAX,
+                    _ => isize::MAX,
+                };
+                if let Err(message) = state.edit_list(|list| list.move_current(steps)) {
                     state.notifications.push_notification(Notification::new(
-                        " deleted item ".into(),
-                        format!(
-                            "deleted item `{}` from todo list with status {}",
-                            item.title(),
-                            item.status()
-                        ),
-                        Duration::from_secs(5),
+                        " move ".into(),
+                        message,
+                        Duration::from_secs(3),
                         NotificationLevel::Warn,
                     ));
                 }
             }
-            KeyCode::Enter | KeyCode::Tab => state.todo_list.toggle_current_status(),
+            Some(Action::Snooze) => {
+                let ids = state.todo_list.target_ids(n);
+                ListView::stop_visual(&mut state);
+                if ids.is_empty() {
+                    return;
+                }
+                let list = state.list_name.clone();
+                state.reminders.snooze(&list, &ids);
+                let minutes = ReminderConfig::current().snooze_minutes;
+                let tasks = match ids.len() {
+                    1 => "the task".to_string(),
+                    count => format!("{} tasks", count),
+                };
+                state.notifications.push_notification(Notification::new(
+                    " snoozed ".into(),
+                    format!("no reminders for {} in the next {} minutes", tasks, minutes),
+                    Duration::from_secs(3),
+                    NotificationLevel::Info,
+                ));
+            }
+            Some(Action::MarkAll) => state.todo_list.mark_all(),
+            Some(Action::MarkMatching) => state.todo_list.mark_matching(),
+            Some(Action::ClearMarks) => state.todo_list.clear_marks(),
+            Some(Action::Undo) => ListView::undo(&mut state, false, n),
+            Some(Action::Redo) => ListView::undo(&mut state, true, n),
             _ => {}
         };
     }
 
+    fn view_event_paste(&mut self, text: &str) {
+        if let Some(command_line) = &mut self.command_line {
+            return command_line.insert_str(text);
+        }
+        match &mut self.switcher {
+            Some(switcher) if switcher.creating() => switcher.input_mut().insert_str(text),
+            Some(_) => {}
+            None if self.searching => {
+                self.search.insert_str(text);
+                self.app_state
+                    .borrow_mut()
+                    .todo_list
+                    .set_filter(self.search.value());
+            }
+            None => {}
+        }
+    }
+
+    // clicking a row selects it, clicking the status of a task moves it to
+    // the next status and clicking a group header folds it, the wheel
+    // scrolls through the rows and clicking the search input focuses it
+    fn view_event_mouse(&mut self, mouse: MouseEvent) {
+        if self.switcher.is_some() || self.command_line.is_some() {
+            return;
+        }
+
+        let position = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.app_state.borrow_mut().todo_list.next(),
+            MouseEventKind::ScrollUp => self.app_state.borrow_mut().todo_list.prev(),
+            MouseEventKind::Down(MouseButton::Left)
+                if self.search_area.is_some_and(|area| area.contains(position)) =>
+            {
+                self.start_search();
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.searching {
+                    self.stop_search(false);
+                }
+
+                let mut state = self.app_state.borrow_mut();
+                match state.todo_list.select_at(position) {
+                    Some(ListClick::Status) => {
+                        state.edit_list(|list| list.cycle_current_status(true))
+                    }
+                    Some(ListClick::Header) => {
+                        state.todo_list.toggle_current_group();
+                    }
+                    Some(ListClick::Item) | None => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
     #[inline]
     fn render_view(&mut self, area: Rect, buf: &mut Buffer) {
         let [list_area, content_area, controls_area] = Layout::vertical([
@@ -103,9 +766,28 @@ impl View for ListView {
             Constraint::Length(1),
         ])
         .areas(area);
+        let [title_area, list_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(list_area);
+        self.render_list_title(title_area, buf);
+
+        // the search input is shown while searching or while a filter is active
+        let list_area = if self.searching || !self.search.value().is_empty() {
+            let [search_area, list_area] =
+                Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(list_area);
+            self.search.render(search_area, buf);
+            self.search_area = Some(search_area);
+            list_area
+        } else {
+            self.search_area = None;
+            list_area
+        };
 
         self.render_controls_line(controls_area, buf);
         self.render_todo_item_content(content_area, buf);
         self.app_state.borrow_mut().todo_list.render(list_area, buf);
+
+        if let Some(switcher) = &mut self.switcher {
+            switcher.render(area, buf);
+        }
     }
 }
@@ -1,25 +1,80 @@
-use ratatui::{crossterm::event::KeyCode, prelude::*};
-
-#[macro_export]
-macro_rules! key_spans {
-    ($( $key:expr, $description:expr ),*) => {
-        vec![
-            $(
-                Span::from(format!(" {} - {} ", $key, $description)),
-                Span::from("|").black(),
-            )*
-        ]
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseEvent},
+    prelude::*,
+};
+
+use crate::{
+    keymap::{Action, Context, Keymap},
+    theme::{Role, Theme},
+};
+
+// the controls line shown at the bottom of the views, generated from the
+// active keymap bindings of the main actions of the given contexts, the
+// entries that don't fit the width are left out, so the help and the other
+// global entries come first and the whole keymap can always be found
+pub fn controls_line(contexts: &[Context], width: u16) -> Line<'static> {
+    let keymap = Keymap::current();
+    let theme = Theme::current();
+    let help = contexts
+        .contains(&Context::Global)
+        .then(|| keymap.keys(Context::Global, Action::Help))
+        .flatten()
+        .map(|keys| (keys, Action::Help.description(Context::Global)));
+    let (global, view): (Vec<Context>, Vec<Context>) = contexts
+        .iter()
+        .partition(|context| **context == Context::Global);
+    let controls = global
+        .into_iter()
+        .chain(view)
+        .flat_map(|context| keymap.main_controls(context));
+
+    let mut spans = Vec::new();
+    let mut used = 0;
+    for (keys, description) in help.into_iter().chain(controls) {
+        let entry = Span::from(format!(" {} - {} ", keys, description));
+        // the entry and its separator
+        used += entry.width() + 1;
+        if used > width as usize {
+            break;
+        }
+        spans.push(entry);
+        spans.push(Span::styled("|", theme.style(Role::ControlsSeparator)));
     }
+
+    Line::from(spans).style(theme.style(Role::Controls))
+}
+
+// views that can be opened by other views, a view requests one by
+// setting `ApplicationState::requested_view` and the application
+// switches to it after the event was handled
+pub enum ViewKind {
+    List,
+    NewTask,
+    // a new task added as a subtask of the item with the id
+    NewSubtask(u64),
+    EditTask(u64),
+    Notifications,
+    Calendar,
 }
 
 pub trait View {
-    fn view_event_key(&mut self, key: KeyCode);
+    fn view_event_key(&mut self, key: KeyEvent);
+
+    // text pasted into the terminal, ignored by views without inputs
+    fn view_event_paste(&mut self, _text: &str) {}
+
+    // clicks and scroll wheel events, ignored by default
+    fn view_event_mouse(&mut self, _mouse: MouseEvent) {}
 
     fn render_view(&mut self, area: Rect, buf: &mut Buffer);
 }
 
+mod calendar;
 mod list;
 mod new_task;
+mod notifications;
 
+pub use calendar::CalendarView;
 pub use list::ListView;
 pub use new_task::NewTaskView;
+pub use notifications::NotificationLogView;
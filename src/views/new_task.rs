@@ -1,43 +1,338 @@
-use ratatui::{crossterm::event::KeyCode, prelude::*, widgets::Widget};
-use std::{cell::RefCell, rc::Rc};
+use chrono::NaiveDate;
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    prelude::*,
+    widgets::Widget,
+};
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
-use super::View;
+use super::{controls_line, View, ViewKind};
 use crate::{
     app::{ApplicationMode, ApplicationState},
-    widgets::Input,
+    keymap::{Action, Context, Keymap},
+    widgets::{
+        parse_tags, Input, Notification, NotificationLevel, Priority, Repeat, TodoItem, DATE_FORMAT,
+    },
 };
 
+// indexes of the form inputs, in focus order
+const TITLE: usize = 0;
+const DESCRIPTION: usize = 1;
+const DUE: usize = 2;
+const REPEAT: usize = 3;
+const PRIORITY: usize = 4;
+const TAGS: usize = 5;
+
 pub struct NewTaskView {
-    title: Input,
-    description: Input,
+    inputs: [Input; 6],
+    // where the inputs were last rendered, used to find the clicked input
+    input_areas: [Rect; 6],
+    // index of the focused input, `None` when not writing
+    focus: Option<usize>,
+    // id of the item being edited, `None` when creating a new item
+    editing: Option<u64>,
+    // id of the item new items are added as subtasks of
+    parent: Option<u64>,
     app_state: Rc<RefCell<ApplicationState>>,
 }
 
+// the parsed form values
+struct TaskFields {
+    title: String,
+    description: String,
+    due: Option<NaiveDate>,
+    repeat: Option<Repeat>,
+    priority: Priority,
+    tags: Vec<String>,
+}
+
 impl NewTaskView {
     pub fn new(app_state: Rc<RefCell<ApplicationState>>) -> Self {
         Self {
-            title: Input::default(),
-            description: Input::default(),
+            inputs: [
+                Input::new("title"),
+                Input::new("description"),
+                Input::new("due (YYYY-MM-DD)"),
+                Input::new("repeat (daily/weekly mon)"),
+                Input::new("priority (none/low/medium/high)"),
+                Input::new("tags (comma separated)"),
+            ],
+            input_areas: [Rect::default(); 6],
+            focus: None,
+            editing: None,
+            parent: None,
             app_state,
         }
     }
+
+    // creates the view with the inputs filled from the item with the given id,
+    // submitting writes the changes back to that item
+    pub fn edit(app_state: Rc<RefCell<ApplicationState>>, id: u64) -> Self {
+        let mut view = Self::new(app_state);
+        let state = Rc::clone(&view.app_state);
+
+        if let Some(item) = state.borrow().todo_list.get(id) {
+            let due = item.due().map(|d| d.format(DATE_FORMAT).to_string());
+            let priority = match item.priority() {
+                Priority::None => String::new(),
+                priority => priority.to_string(),
+            };
+
+            view.inputs[TITLE].set_value(item.title().clone());
+            view.inputs[DESCRIPTION].set_value(item.description().clone());
+            view.inputs[DUE].set_value(due.unwrap_or_default());
+            view.inputs[REPEAT].set_value(item.repeat().map(Repeat::to_string).unwrap_or_default());
+            view.inputs[PRIORITY].set_value(priority);
+            view.inputs[TAGS].set_value(item.tags().join(", "));
+            view.editing = Some(id);
+        }
+
+        if view.editing.is_some() {
+            view.focus_next();
+        }
+        view
+    }
+
+    // creates the view for adding a subtask to the item with the given
+    // id, the view goes back to the list once the subtask is added
+    pub fn subtask(app_state: Rc<RefCell<ApplicationState>>, parent: u64) -> Self {
+        let mut view = Self::new(app_state);
+        view.parent = Some(parent);
+        view.focus_next();
+        view
+    }
+
+    // moves the focus to the next input, when no input is focused
+    // the title is focused and the application enters writing mode
+    fn focus_next(&mut self) {
+        let next = match self.focus {
+            Some(i) => (i + 1) % self.inputs.len(),
+            None => TITLE,
+        };
+        self.set_focus(Some(next));
+        self.app_state.borrow_mut().mode = ApplicationMode::Writing;
+    }
+
+    fn stop_writing(&mut self) {
+        self.set_focus(None);
+        self.app_state.borrow_mut().mode = ApplicationMode::Normal;
+    }
+
+    fn set_focus(&mut self, focus: Option<usize>) {
+        for (i, input) in self.inputs.iter_mut().enumerate() {
+            input.set_focused(Some(i) == focus);
+        }
+        self.focus = focus;
+    }
+
+    // leaves the view without saving the edited item or subtask
+    fn cancel_edit(&mut self) {
+        self.stop_writing();
+        self.app_state.borrow_mut().requested_view = Some(ViewKind::List);
+    }
+
+    fn focused_input(&mut self) -> Option<&mut Input> {
+        self.focus.map(|i| &mut self.inputs[i])
+    }
+
+    // parses the inputs, returns a message describing
+    // the first invalid input on failure
+    fn parse_fields(&self) -> Result<TaskFields, String> {
+        let title = self.inputs[TITLE].value().trim().to_string();
+        if title.is_empty() {
+            return Err("a task must have a title".into());
+        }
+
+        let due = match self.inputs[DUE].value().trim() {
+            "" => None,
+            due => Some(
+                NaiveDate::parse_from_str(due, DATE_FORMAT)
+                    .map_err(|_| format!("invalid due date `{}`, expected YYYY-MM-DD", due))?,
+            ),
+        };
+
+        let repeat = match self.inputs[REPEAT].value().trim() {
+            "" => None,
+            repeat => Some(repeat.parse()?),
+        };
+
+        Ok(TaskFields {
+            title,
+            description: self.inputs[DESCRIPTION].value().trim().to_string(),
+            due,
+            repeat,
+            priority: self.inputs[PRIORITY].value().parse()?,
+            tags: parse_tags(self.inputs[TAGS].value()),
+        })
+    }
+
+    // creates a new todo item from the inputs and adds it to the todo
+    // list, the inputs are cleared afterwards, when editing the changes are
+    // written to the edited item and the view goes back to the list,
+    // subtasks are added under their parent before going back to the list
+    fn submit(&mut self) {
+        let mut state = self.app_state.borrow_mut();
+        let fields = match self.parse_fields() {
+            Ok(fields) => fields,
+            Err(message) => {
+                state.notifications.push_notification(Notification::new(
+                    " invalid task ".into(),
+                    message,
+                    Duration::from_secs(3),
+                    NotificationLevel::Error,
+                ));
+                return;
+            }
+        };
+
+        if let Some(id) = self.editing {
+            state.edit_list(|list| {
+                if let Some(item) = list.get_mut(id) {
+                    fields.apply(item);
+                }
+            });
+            state.requested_view = Some(ViewKind::List);
+            return;
+        }
+
+        let title = fields.title.clone();
+        let mut item = TodoItem::new(String::new(), String::new());
+        fields.apply(&mut item);
+
+        if let Some(parent) = self.parent {
+            let added = state.edit_list(|list| list.add_child(parent, item));
+            let notification = match added {
+                Some(_) => Notification::new(
+                    " added subtask ".into(),
+                    format!("added subtask `{}` to task #{}", title, parent),
+                    Duration::from_secs(3),
+                    NotificationLevel::Info,
+                ),
+                None => Notification::new(
+                    " invalid task ".into(),
+                    format!("task #{} no longer exists", parent),
+                    Duration::from_secs(3),
+                    NotificationLevel::Error,
+                ),
+            };
+            state.notifications.push_notification(notification);
+            state.requested_view = Some(ViewKind::List);
+            return;
+        }
+
+        state.notifications.push_notification(Notification::new(
+            " added item ".into(),
+            format!("added item `{}` to todo list", title),
+            Duration::from_secs(3),
+            NotificationLevel::Info,
+        ));
+        state.edit_list(|list| list.add(item));
+        drop(state);
+
+        self.inputs.iter_mut().for_each(Input::clear);
+        self.stop_writing();
+    }
+
+    fn render_controls_line(&self, area: Rect, buf: &mut Buffer) {
+        // the global bindings only work while not writing
+        let line = if self.focus.is_some() {
+            controls_line(&[Context::Form], area.width)
+        } else {
+            controls_line(&[Context::Form, Context::Global], area.width)
+        };
+        line.render(area, buf);
+    }
+}
+
+impl TaskFields {
+    // sets the fields that differ from the item, so saving an
+    // unchanged task doesn't touch it or record an undo step
+    fn apply(self, item: &mut TodoItem) {
+        if *item.title() != self.title {
+            item.set_title(self.title);
+        }
+        if *item.description() != self.description {
+            item.set_description(self.description);
+        }
+        if item.due() != self.due {
+            item.set_due(self.due);
+        }
+        if item.repeat() != self.repeat.as_ref() {
+            item.set_repeat(self.repeat);
+        }
+        if item.priority() != self.priority {
+            item.set_priority(self.priority);
+        }
+        if item.tags() != self.tags {
+            item.set_tags(self.tags);
+        }
+    }
 }
 
 impl View for NewTaskView {
-    fn view_event_key(&mut self, key: KeyCode) {
-        match key {
-            KeyCode::Tab => {
-                self.title.toggle_focuse();
-                self.app_state.borrow_mut().mode = ApplicationMode::Writing;
+    fn view_event_key(&mut self, key: KeyEvent) {
+        match Keymap::current().action(Context::Form, &key) {
+            Some(Action::NextField) => self.focus_next(),
+            Some(Action::Cancel) if self.editing.is_some() || self.parent.is_some() => {
+                self.cancel_edit()
             }
-            _ => {}
+            Some(Action::Cancel) => self.stop_writing(),
+            Some(Action::Submit) => self.submit(),
+            _ => {
+                if let Some(input) = self.focused_input() {
+                    input.handle_key(key);
+                }
+            }
+        }
+    }
+
+    fn view_event_paste(&mut self, text: &str) {
+        if let Some(input) = self.focused_input() {
+            input.insert_str(text);
+        }
+    }
+
+    // clicking an input focuses it
+    fn view_event_mouse(&mut self, mouse: MouseEvent) {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return;
+        }
+
+        let position = Position::new(mouse.column, mouse.row);
+        if let Some(i) = self.input_areas.iter().position(|a| a.contains(position)) {
+            self.set_focus(Some(i));
+            self.app_state.borrow_mut().mode = ApplicationMode::Writing;
         }
     }
 
     fn render_view(&mut self, area: Rect, buf: &mut Buffer) {
-        let [title_area, description_area] =
-            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
-        self.title.render(title_area, buf);
-        self.description.render(description_area, buf);
+        let [title_area, description_area, details_area, _, controls_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        let [due_area, repeat_area, priority_area, tags_area] = Layout::horizontal([
+            Constraint::Percentage(16),
+            Constraint::Percentage(24),
+            Constraint::Percentage(28),
+            Constraint::Percentage(32),
+        ])
+        .areas(details_area);
+
+        self.input_areas = [
+            title_area,
+            description_area,
+            due_area,
+            repeat_area,
+            priority_area,
+            tags_area,
+        ];
+        for (input, area) in self.inputs.iter().zip(self.input_areas) {
+            input.render(area, buf);
+        }
+        self.render_controls_line(controls_area, buf);
     }
 }
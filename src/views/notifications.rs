@@ -0,0 +1,123 @@
+use ratatui::{
+    crossterm::event::{KeyEvent, MouseEvent, MouseEventKind},
+    prelude::*,
+    widgets::{List, ListItem, ListState},
+};
+use std::{cell::RefCell, rc::Rc};
+
+use super::{controls_line, View};
+use crate::{
+    app::ApplicationState,
+    keymap::{Action, Context, Keymap},
+    theme::{Role, Theme},
+    widgets::{CenteredText, TIMESTAMP_FORMAT},
+};
+
+// lists the past notifications, newest first, a notification
+// can be pinned from here to show it again until it is dismissed
+pub struct NotificationLogView {
+    app_state: Rc<RefCell<ApplicationState>>,
+    widget_state: ListState,
+}
+
+impl NotificationLogView {
+    pub fn new(app_state: Rc<RefCell<ApplicationState>>) -> Self {
+        let selected = (!app_state.borrow().notifications.history().is_empty()).then_some(0);
+        Self {
+            app_state,
+            widget_state: ListState::default().with_selected(selected),
+        }
+    }
+
+    // the id of the selected notification, the rows are shown newest first
+    fn selected_id(&self) -> Option<u64> {
+        let state = self.app_state.borrow();
+        let history = state.notifications.history();
+        let i = self.widget_state.selected()?;
+        history.iter().rev().nth(i).map(|n| n.id())
+    }
+
+    fn render_title(&self, area: Rect, buf: &mut Buffer) {
+        let state = self.app_state.borrow();
+        let theme = Theme::current();
+        Line::from(vec![
+            Span::from(" notifications ").bold(),
+            Span::styled(
+                format!("({})", state.notifications.history().len()),
+                theme.style(Role::Accent),
+            ),
+        ])
+        .style(theme.style(Role::Base))
+        .render(area, buf);
+    }
+}
+
+impl View for NotificationLogView {
+    fn view_event_key(&mut self, key: KeyEvent) {
+        match Keymap::current().action(Context::Notifications, &key) {
+            Some(Action::Up) => self.widget_state.select_previous(),
+            Some(Action::Down) => self.widget_state.select_next(),
+            Some(Action::Top) => self.widget_state.select_first(),
+            Some(Action::Bottom) => self.widget_state.select_last(),
+            Some(Action::PinNotification) => {
+                if let Some(id) = self.selected_id() {
+                    self.app_state.borrow_mut().notifications.toggle_pin(id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn view_event_mouse(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.widget_state.select_next(),
+            MouseEventKind::ScrollUp => self.widget_state.select_previous(),
+            _ => {}
+        }
+    }
+
+    fn render_view(&mut self, area: Rect, buf: &mut Buffer) {
+        let [title_area, log_area, controls_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        self.render_title(title_area, buf);
+        controls_line(
+            &[Context::Notifications, Context::Global],
+            controls_area.width,
+        )
+        .render(controls_area, buf);
+
+        let theme = Theme::current();
+        let state = self.app_state.borrow();
+        let history = state.notifications.history();
+        if history.is_empty() {
+            CenteredText::new(Text::from("no notifications yet").style(theme.style(Role::Hint)))
+                .render(log_area, buf);
+            return;
+        }
+
+        let items = history.iter().rev().map(|notification| {
+            let pin = if notification.pinned() { "* " } else { "  " };
+            ListItem::new(Line::from(vec![
+                Span::from(pin),
+                Span::styled(
+                    notification.created().format(TIMESTAMP_FORMAT).to_string(),
+                    theme.style(Role::Accent),
+                ),
+                Span::styled(
+                    format!(" {:<5} ", notification.level()),
+                    theme.style(notification.level().role()),
+                ),
+                Span::from(format!("{}: ", notification.title().trim())).bold(),
+                Span::from(notification.content().replace('\n', " ")),
+            ]))
+        });
+        let list = List::new(items)
+            .highlight_symbol("> ")
+            .highlight_style(theme.style(Role::Selected));
+        StatefulWidget::render(list, log_area, buf, &mut self.widget_state);
+    }
+}
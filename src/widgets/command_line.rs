@@ -0,0 +1,110 @@
+use ratatui::{crossterm::event::KeyEvent, prelude::*, widgets::Widget};
+
+use super::Input;
+use crate::theme::{Role, Theme};
+
+// the `:` line shown instead of the controls line while a command is
+// entered, tab completes the last word and cycles through the candidates
+#[derive(Default)]
+pub struct CommandLine {
+    input: Input,
+    completion: Option<Completion>,
+}
+
+// the candidates of the last completion, cycled by completing again
+struct Completion {
+    // byte index in the value where the completed word starts
+    start: usize,
+    candidates: Vec<String>,
+    index: usize,
+}
+
+impl CommandLine {
+    // starts with the text already entered, like the name of a command
+    pub fn new(text: &str) -> Self {
+        let mut command_line = Self::default();
+        command_line.input.set_value(text.to_string());
+        command_line.input.set_focused(true);
+        command_line
+    }
+
+    pub fn value(&self) -> &str {
+        self.input.value()
+    }
+
+    // applies the editing key to the input, the completion
+    // candidates are dropped since the word changed
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.completion = None;
+        self.input.handle_key(key)
+    }
+
+    pub fn insert_str(&mut self, text: &str) {
+        self.completion = None;
+        self.input.insert_str(text);
+    }
+
+    pub fn completing(&self) -> bool {
+        self.completion.is_some()
+    }
+
+    // replaces the word starting at `start` with the first candidate,
+    // nothing changes when there are no candidates
+    pub fn start_completion(&mut self, start: usize, candidates: Vec<String>) {
+        if candidates.is_empty() {
+            return;
+        }
+        self.completion = Some(Completion {
+            start,
+            candidates,
+            index: 0,
+        });
+        self.apply_completion();
+    }
+
+    // replaces the completed word with the next candidate
+    pub fn next_completion(&mut self) {
+        if let Some(completion) = &mut self.completion {
+            completion.index = (completion.index + 1) % completion.candidates.len();
+        }
+        self.apply_completion();
+    }
+
+    fn apply_completion(&mut self) {
+        let Some(completion) = &self.completion else {
+            return;
+        };
+        let mut value = self.input.value()[..completion.start].to_string();
+        value.push_str(&completion.candidates[completion.index]);
+        self.input.set_value(value);
+    }
+}
+
+impl Widget for &CommandLine {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let theme = Theme::current();
+        let mut spans = vec![Span::from(":")];
+        spans.extend(self.input.cursor_spans());
+
+        // the candidates are listed after the command, the current one highlighted
+        if let Some(completion) = &self.completion {
+            spans.push(Span::from("   "));
+            for (i, candidate) in completion.candidates.iter().enumerate() {
+                let style = if i == completion.index {
+                    theme.style(Role::Selected)
+                } else {
+                    theme.style(Role::Hint)
+                };
+                spans.push(Span::styled(candidate.clone(), style));
+                spans.push(Span::from(" "));
+            }
+        }
+
+        Line::from(spans)
+            .style(theme.style(Role::Base))
+            .render(area, buf);
+    }
+}
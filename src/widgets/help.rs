@@ -0,0 +1,95 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    keymap::{Context, Keymap},
+    theme::{Role, Theme},
+};
+
+// popup listing the bindings of every context, the controls
+// line only shows the main actions
+#[derive(Default)]
+pub struct HelpPopup {
+    scroll: u16,
+    // the scroll that shows the last line at the bottom, updated on render
+    max_scroll: u16,
+}
+
+impl HelpPopup {
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.scroll = (self.scroll + 1).min(self.max_scroll);
+    }
+
+    // a header for every context followed by its keys and descriptions
+    fn lines() -> Vec<Line<'static>> {
+        let keymap = Keymap::current();
+        let theme = Theme::current();
+        let mut lines = Vec::new();
+
+        for context in Context::ALL {
+            let controls = keymap.controls(context);
+            if controls.is_empty() {
+                continue;
+            }
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::styled(context.to_string(), theme.style(Role::Accent)).bold());
+
+            let width = controls
+                .iter()
+                .map(|(keys, _)| keys.chars().count())
+                .max()
+                .unwrap_or(0);
+            for (keys, description) in controls {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:<width$}  ", keys), theme.style(Role::Accent)),
+                    Span::from(description),
+                ]));
+            }
+        }
+        lines
+    }
+}
+
+impl Widget for &mut HelpPopup {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let theme = Theme::current();
+        let [_, area, _] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(2),
+        ])
+        .areas(area);
+        let [_, area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(56),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        let block = Block::bordered()
+            .title(" keys ")
+            .border_style(theme.style(Role::FocusedBorder))
+            .padding(Padding::horizontal(1))
+            .style(theme.style(Role::Base));
+        let lines = HelpPopup::lines();
+        self.max_scroll = (lines.len() as u16).saturating_sub(block.inner(area).height);
+        self.scroll = self.scroll.min(self.max_scroll);
+
+        Widget::render(Clear, area, buf);
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((self.scroll, 0))
+            .render(area, buf);
+    }
+}
@@ -1,23 +1,205 @@
 use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
     prelude::*,
     widgets::{Block, Padding, Paragraph, Widget},
 };
+use unicode_width::UnicodeWidthStr;
+
+use crate::theme::{Role, Theme};
 
-#[allow(dead_code)]
 #[derive(Default)]
 pub struct Input {
+    title: String,
     value: String,
+    // byte index into `value`, always kept on a char boundary
     cursor_index: usize,
     focused: bool,
 }
 
 impl Input {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
     pub fn toggle_focuse(&mut self) {
         self.focused = !self.focused;
     }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
     pub fn focused(&self) -> bool {
         self.focused
     }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    // replaces the input value and moves the cursor to the end of it
+    pub fn set_value(&mut self, value: String) {
+        self.cursor_index = value.len();
+        self.value = value;
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor_index = 0;
+    }
+
+    // applies the editing key to the input, returns `false`
+    // if the key is not an editing key and was ignored
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        match key.code {
+            KeyCode::Left if ctrl => self.move_word_left(),
+            KeyCode::Right if ctrl => self.move_word_right(),
+            KeyCode::Char('b') if alt => self.move_word_left(),
+            KeyCode::Char('f') if alt => self.move_word_right(),
+            KeyCode::Char('a') if ctrl => self.move_home(),
+            KeyCode::Char('e') if ctrl => self.move_end(),
+            KeyCode::Char('w') if ctrl => self.delete_word_backward(),
+            KeyCode::Char('u') if ctrl => self.delete_to_home(),
+            KeyCode::Backspace if ctrl || alt => self.delete_word_backward(),
+            KeyCode::Char(c) if !ctrl && !alt => self.insert(c),
+            KeyCode::Backspace => self.delete_backward(),
+            KeyCode::Delete => self.delete_forward(),
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right => self.move_right(),
+            KeyCode::Home => self.move_home(),
+            KeyCode::End => self.move_end(),
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.value.insert(self.cursor_index, c);
+        self.cursor_index += c.len_utf8();
+    }
+
+    // inserts pasted text at the cursor, the input is a single line
+    // so line breaks and other control chars are replaced with spaces
+    pub fn insert_str(&mut self, text: &str) {
+        let text: String = text
+            .replace("\r\n", " ")
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+        self.value.insert_str(self.cursor_index, &text);
+        self.cursor_index += text.len();
+    }
+
+    pub fn delete_backward(&mut self) {
+        if let Some(start) = self.prev_boundary(self.cursor_index) {
+            self.value.drain(start..self.cursor_index);
+            self.cursor_index = start;
+        }
+    }
+
+    pub fn delete_forward(&mut self) {
+        if let Some(end) = self.next_boundary(self.cursor_index) {
+            self.value.drain(self.cursor_index..end);
+        }
+    }
+
+    pub fn delete_word_backward(&mut self) {
+        let start = self.word_start_before(self.cursor_index);
+        self.value.drain(start..self.cursor_index);
+        self.cursor_index = start;
+    }
+
+    pub fn delete_to_home(&mut self) {
+        self.value.drain(..self.cursor_index);
+        self.cursor_index = 0;
+    }
+
+    pub fn move_left(&mut self) {
+        if let Some(i) = self.prev_boundary(self.cursor_index) {
+            self.cursor_index = i;
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if let Some(i) = self.next_boundary(self.cursor_index) {
+            self.cursor_index = i;
+        }
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor_index = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor_index = self.value.len();
+    }
+
+    pub fn move_word_left(&mut self) {
+        self.cursor_index = self.word_start_before(self.cursor_index);
+    }
+
+    pub fn move_word_right(&mut self) {
+        let rest = &self.value[self.cursor_index..];
+        let word_start = rest
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(rest.len());
+        let word_len = rest[word_start..]
+            .find(char::is_whitespace)
+            .unwrap_or(rest.len() - word_start);
+        self.cursor_index += word_start + word_len;
+    }
+
+    // byte index of the char before `index`
+    fn prev_boundary(&self, index: usize) -> Option<usize> {
+        self.value[..index].char_indices().last().map(|(i, _)| i)
+    }
+
+    // byte index of the char after `index`
+    fn next_boundary(&self, index: usize) -> Option<usize> {
+        self.value[index..]
+            .chars()
+            .next()
+            .map(|c| index + c.len_utf8())
+    }
+
+    // byte index where the word that ends before `index` begins,
+    // whitespace between the word and `index` is skipped
+    fn word_start_before(&self, index: usize) -> usize {
+        let before = self.value[..index].trim_end();
+        match before.rfind(char::is_whitespace) {
+            Some(i) => i + before[i..].chars().next().map_or(0, char::len_utf8),
+            None => 0,
+        }
+    }
+
+    // the value with the cursor drawn on it, for inputs drawn without a box
+    pub fn cursor_spans(&self) -> Vec<Span<'_>> {
+        let (before, cursor, after) = self.split_at_cursor();
+        vec![
+            Span::from(before),
+            Span::styled(cursor, Theme::current().style(Role::Cursor)),
+            Span::from(after),
+        ]
+    }
+
+    // the value split around the cursor, the middle part is the
+    // char under the cursor or a space if the cursor is at the end
+    fn split_at_cursor(&self) -> (&str, &str, &str) {
+        let (before, rest) = self.value.split_at(self.cursor_index);
+        match self.next_boundary(self.cursor_index) {
+            Some(end) => {
+                let len = end - self.cursor_index;
+                (before, &rest[..len], &rest[len..])
+            }
+            None => (before, " ", ""),
+        }
+    }
 }
 
 impl Widget for &Input {
@@ -25,19 +207,33 @@ impl Widget for &Input {
     where
         Self: Sized,
     {
+        let theme = Theme::current();
         let box_borders = if self.focused {
-            Style::new().light_blue()
+            theme.style(Role::FocusedBorder)
         } else {
-            Style::new().white()
+            theme.style(Role::Border)
         };
+        let block = Block::bordered()
+            .title(self.title.clone())
+            .padding(Padding::horizontal(2))
+            .border_style(box_borders);
+
+        if !self.focused {
+            Paragraph::new(self.value.clone())
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        // scroll horizontally so the cursor is always visible
+        let (before, _, _) = self.split_at_cursor();
+        let inner_width = block.inner(area).width;
+        let cursor_column = before.width() as u16;
+        let scroll = (cursor_column + 1).saturating_sub(inner_width);
 
-        Paragraph::new(self.value.clone())
-            .block(
-                Block::bordered()
-                    .title("input")
-                    .padding(Padding::horizontal(2))
-                    .border_style(box_borders),
-            )
+        Paragraph::new(Line::from(self.cursor_spans()))
+            .block(block)
+            .scroll((0, scroll))
             .render(area, buf);
     }
 }
@@ -1,9 +1,22 @@
 mod centered;
+mod command_line;
+mod help;
 mod input;
 mod notification;
+mod ordering;
+mod repeat;
+mod switcher;
 mod todo;
 
 pub use centered::CenteredText;
+pub use command_line::CommandLine;
+pub use help::HelpPopup;
 pub use input::Input;
-pub use notification::{Notification, NotificationLevel, NotificationStack};
-pub use todo::{TodoItem, TodoList};
+pub use notification::{Notification, NotificationConfig, NotificationLevel, NotificationStack};
+pub use ordering::{GroupBy, ListSettings, SortKey};
+pub use repeat::Repeat;
+pub use switcher::{ListSwitcher, SwitcherPurpose};
+pub use todo::{
+    parse_tags, ListClick, Priority, TodoItem, TodoItemStatus, TodoList, DATE_FORMAT,
+    TIMESTAMP_FORMAT,
+};
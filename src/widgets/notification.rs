@@ -1,13 +1,47 @@
+use chrono::{DateTime, Local};
 use ratatui::{
     prelude::*,
     widgets::{Block, Clear, Padding, Paragraph, Widget},
 };
+use serde::Deserialize;
 use std::{
-    iter::zip,
+    fmt,
     time::{Duration, Instant},
 };
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-#[derive(Default, Clone)]
+use crate::{
+    config,
+    theme::{Role, Theme},
+};
+
+// how many past notifications are kept for the notification log
+const MAX_HISTORY: usize = 200;
+// the columns around the content, the two borders and the left padding
+const FRAME_WIDTH: u16 = 3;
+
+// the corner of the screen the notifications are stacked from,
+// the first notification of the stack is the closest to it
+#[derive(Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationCorner {
+    #[default]
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+}
+
+// the `[notifications]` table of the config
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationConfig {
+    pub corner: NotificationCorner,
+    // the widest a notification box can be, longer content is wrapped
+    pub max_width: u16,
+}
+
+#[derive(Default, Clone, Copy)]
 pub enum NotificationLevel {
     #[default]
     Info,
@@ -21,34 +55,109 @@ pub struct Notification {
     content: String,
     duration: Duration,
     initilized_time: Instant,
+    // when the notification was pushed, shown in the notification log
+    created: DateTime<Local>,
     level: NotificationLevel,
+    // pinned notifications are shown until they are dismissed
+    pinned: bool,
+    // set by the stack, identifies the notification in the history
+    id: u64,
 }
 
 #[derive(Default)]
 pub struct NotificationStack {
     notifications: Vec<Notification>,
+    // where the shown notifications were last rendered, in stack order
+    areas: Vec<Rect>,
+    // every pushed notification, oldest first, shown or not
+    history: Vec<Notification>,
+    next_id: u64,
 }
 
 impl NotificationStack {
     // creates a new notification on the stack
     // with the defined duration in the notification
-    pub fn push_notification(&mut self, notification: Notification) {
+    pub fn push_notification(&mut self, mut notification: Notification) {
+        self.next_id += 1;
+        notification.id = self.next_id;
+
+        self.history.push(notification.clone());
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
         self.notifications.push(notification);
     }
 
+    // the past notifications, oldest first
+    pub fn history(&self) -> &[Notification] {
+        &self.history
+    }
+
+    // removes the notification at the top of the stack,
+    // returns false if no notification is shown
+    pub fn dismiss_top(&mut self) -> bool {
+        if self.notifications.is_empty() {
+            return false;
+        }
+        self.notifications.remove(0);
+        self.areas.clear();
+        true
+    }
+
+    // removes all the shown notifications, pinned ones included,
+    // they are kept in the history
+    pub fn clear(&mut self) -> bool {
+        self.areas.clear();
+        !std::mem::take(&mut self.notifications).is_empty()
+    }
+
+    // pins the notification at the top of the stack or unpins it,
+    // returns whether it is pinned now or `None` if none is shown
+    pub fn toggle_pin_top(&mut self) -> Option<bool> {
+        let id = self.notifications.first()?.id;
+        self.toggle_pin(id)
+    }
+
+    // pins the notification with the id or unpins it, a pinned
+    // notification that already timed out is shown again,
+    // returns whether it is pinned now or `None` if there is no such notification
+    pub fn toggle_pin(&mut self, id: u64) -> Option<bool> {
+        let entry = self.history.iter_mut().find(|n| n.id == id)?;
+        entry.pinned = !entry.pinned;
+        let pinned = entry.pinned;
+
+        match self.notifications.iter_mut().find(|n| n.id == id) {
+            Some(shown) => shown.pinned = pinned,
+            None if pinned => self.notifications.push(entry.clone()),
+            None => {}
+        }
+        Some(pinned)
+    }
+
     // returns a boolean value indicating if the stack is empty
     pub fn is_empty(&self) -> bool {
         self.notifications.is_empty()
     }
 
-    // remove notification from stack which duration time exceeded
-    fn remove_timedout_notifications(&mut self) {
-        self.notifications = self
-            .notifications
-            .iter()
-            .filter(|n| n.should_be_displayed())
-            .cloned()
-            .collect();
+    // removes the notification rendered at the position,
+    // returns false if there is no notification there
+    pub fn dismiss_at(&mut self, position: Position) -> bool {
+        match self.areas.iter().position(|area| area.contains(position)) {
+            Some(i) if i < self.notifications.len() => {
+                self.notifications.remove(i);
+                self.areas.clear();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // remove notification from stack which duration time exceeded,
+    // returns true if any notification was removed
+    pub fn remove_expired(&mut self) -> bool {
+        let count = self.notifications.len();
+        self.notifications.retain(Notification::should_be_displayed);
+        self.notifications.len() != count
     }
 }
 
@@ -64,13 +173,43 @@ impl Notification {
             content,
             duration,
             initilized_time: Instant::now(),
+            created: Local::now(),
             level,
+            pinned: false,
+            id: 0,
         }
     }
 
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn created(&self) -> DateTime<Local> {
+        self.created
+    }
+
+    pub fn level(&self) -> NotificationLevel {
+        self.level
+    }
+
+    pub fn pinned(&self) -> bool {
+        self.pinned
+    }
+
     // boolean value indicating if the notification
-    // duration was exceeded
+    // duration was exceeded, pinned notifications never time out
     pub fn should_be_displayed(&self) -> bool {
+        if self.pinned {
+            return true;
+        }
         let now = Instant::now();
         match now.checked_duration_since(self.initilized_time) {
             Some(d) => d < self.duration,
@@ -78,13 +217,125 @@ impl Notification {
         }
     }
 
-    // returns the area with minimum required width
-    fn limit_area_width(&self, area: Rect) -> Rect {
-        Layout::horizontal([
-            Constraint::Fill(1),
-            Constraint::Length((self.content.len() + 5) as u16),
-        ])
-        .split(area)[1]
+    // the width and height of the box showing the notification, the width
+    // fits the widest line of the content or the title up to `max_width`
+    // and the height fits the content wrapped to that width
+    fn size(&self, max_width: u16) -> (u16, u16) {
+        let content_width = self.content.lines().map(|l| l.width()).max().unwrap_or(0);
+        let natural = (content_width as u16 + FRAME_WIDTH).max(self.title.width() as u16 + 2);
+        let width = natural.min(max_width).max(FRAME_WIDTH + 1);
+
+        let lines = wrap_text(&self.content, (width - FRAME_WIDTH) as usize).len();
+        (width, lines as u16 + 2)
+    }
+}
+
+impl NotificationConfig {
+    pub fn current() -> &'static NotificationConfig {
+        &config::get().notifications
+    }
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            corner: NotificationCorner::default(),
+            max_width: 60,
+        }
+    }
+}
+
+impl NotificationCorner {
+    fn is_bottom(self) -> bool {
+        matches!(
+            self,
+            NotificationCorner::BottomRight | NotificationCorner::BottomLeft
+        )
+    }
+
+    fn is_left(self) -> bool {
+        matches!(
+            self,
+            NotificationCorner::TopLeft | NotificationCorner::BottomLeft
+        )
+    }
+
+    // the area of the given size placed `offset` rows away from the corner of `area`
+    fn place(self, area: Rect, width: u16, height: u16, offset: u16) -> Rect {
+        let x = if self.is_left() {
+            area.x
+        } else {
+            area.right() - width
+        };
+        let y = if self.is_bottom() {
+            area.bottom() - offset - height
+        } else {
+            area.y + offset
+        };
+        Rect::new(x, y, width, height)
+    }
+}
+
+// splits the text into lines no wider than `width` columns, breaking at
+// spaces where possible and inside words that don't fit on a line
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut line_width = 0;
+
+        for word in paragraph.split(' ') {
+            let word_width = word.width();
+            if line_width > 0 && line_width + 1 + word_width <= width {
+                line.push(' ');
+                line.push_str(word);
+                line_width += 1 + word_width;
+                continue;
+            }
+            if line_width > 0 {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+
+            for c in word.chars() {
+                let char_width = c.width().unwrap_or(0);
+                if line_width + char_width > width {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                line.push(c);
+                line_width += char_width;
+            }
+        }
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+impl NotificationLevel {
+    // the theme role of the notification border and the log level
+    pub fn role(self) -> Role {
+        match self {
+            NotificationLevel::Info => Role::Info,
+            NotificationLevel::Warn => Role::Warn,
+            NotificationLevel::Error => Role::Error,
+        }
+    }
+}
+
+impl fmt::Display for NotificationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotificationLevel::Info => write!(f, "info"),
+            NotificationLevel::Warn => write!(f, "warn"),
+            NotificationLevel::Error => write!(f, "error"),
+        }
     }
 }
 
@@ -93,22 +344,53 @@ impl Widget for &mut NotificationStack {
     where
         Self: Sized,
     {
-        self.remove_timedout_notifications();
-        if self.is_empty() {
+        // the screen is too narrow for any content
+        if self.is_empty() || area.width <= FRAME_WIDTH {
+            self.areas.clear();
             return;
         }
 
-        let max_notifications = area.height / 3;
-        let mut constraints = Vec::new();
+        let config = NotificationConfig::current();
+        let max_width = config.max_width.min(area.width);
+        let corner = config.corner;
 
-        for _ in 0..max_notifications {
-            constraints.push(Constraint::Ratio(1, max_notifications as u32));
+        // the notifications are stacked from the corner while they fit
+        let mut placed = Vec::new();
+        let mut used = 0;
+        for notification in &self.notifications {
+            let (width, height) = notification.size(max_width);
+            let height = height.min(area.height);
+            if used + height > area.height {
+                break;
+            }
+            placed.push(corner.place(area, width, height, used));
+            used += height;
         }
 
-        let areas = Layout::vertical(constraints).split(area);
-        for (area, notification) in zip(areas.iter(), &self.notifications) {
+        // the notifications that don't fit are counted in a line after the
+        // last shown one, which makes room for it when the screen is full
+        let mut hidden = self.notifications.len() - placed.len();
+        if hidden > 0 && used == area.height {
+            if let Some(last) = placed.pop() {
+                used -= last.height;
+                hidden += 1;
+            }
+        }
+
+        self.areas = placed;
+        for (notification, area) in self.notifications.iter().zip(&self.areas) {
             notification.render(*area, buf);
         }
+
+        if hidden > 0 && used < area.height {
+            let more = format!(" +{} more ", hidden);
+            let width = (more.len() as u16).min(area.width);
+            let more_area = corner.place(area, width, 1, used);
+            Widget::render(Clear, more_area, buf);
+            Line::styled(more, Theme::current().style(Role::Hint))
+                .style(Theme::current().style(Role::Base))
+                .render(more_area, buf);
+        }
     }
 }
 
@@ -117,22 +399,24 @@ impl Widget for &Notification {
     where
         Self: Sized,
     {
-        let area = self.limit_area_width(area);
+        let theme = Theme::current();
+        let mut block = Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Thick)
+            .title(self.title.clone())
+            .padding(Padding::left(1))
+            .border_style(theme.style(self.level.role()))
+            .style(theme.style(Role::Base));
+        if self.pinned {
+            block = block.title_bottom(Line::from(" pinned ").right_aligned());
+        }
+
+        let content_width = area.width.saturating_sub(FRAME_WIDTH) as usize;
+        let lines: Vec<Line> = wrap_text(&self.content, content_width)
+            .into_iter()
+            .map(Line::from)
+            .collect();
+
         Widget::render(Clear, area, buf);
-        Paragraph::new(self.content.clone())
-            .block(
-                Block::bordered()
-                    .border_type(ratatui::widgets::BorderType::Thick)
-                    .title(self.title.clone())
-                    .padding(Padding::left(1))
-                    .border_style(match self.level {
-                        NotificationLevel::Info => Style::default().light_blue(),
-                        NotificationLevel::Warn => Style::default().light_yellow(),
-                        NotificationLevel::Error => Style::default().light_red(),
-                    })
-                    .white()
-                    .on_black(),
-            )
-            .render(area, buf);
+        Paragraph::new(lines).block(block).render(area, buf);
     }
 }
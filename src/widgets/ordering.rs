@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, str::FromStr};
+
+use super::TodoItem;
+use crate::workflow::Workflow;
+
+// label of the tag group holding the items without tags
+pub const UNTAGGED_GROUP: &str = "untagged";
+
+// the order the todo list items are shown in
+#[derive(Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    // the order the items were added in
+    #[default]
+    Manual,
+    Status,
+    Priority,
+    Due,
+    Created,
+    Alphabetical,
+}
+
+// how the todo list items are grouped under headers
+#[derive(Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    #[default]
+    None,
+    Status,
+    Tag,
+}
+
+// the list display settings that are saved with the list
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListSettings {
+    pub sort: SortKey,
+    pub group: GroupBy,
+    // labels of the collapsed group headers
+    pub collapsed: Vec<String>,
+    // ids of the items with folded subtasks
+    pub folded: Vec<u64>,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 6] = [
+        SortKey::Manual,
+        SortKey::Status,
+        SortKey::Priority,
+        SortKey::Due,
+        SortKey::Created,
+        SortKey::Alphabetical,
+    ];
+
+    // the name used in the data file and the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            SortKey::Manual => "manual",
+            SortKey::Status => "status",
+            SortKey::Priority => "priority",
+            SortKey::Due => "due",
+            SortKey::Created => "created",
+            SortKey::Alphabetical => "alphabetical",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let i = SortKey::ALL.iter().position(|k| *k == self).unwrap_or(0);
+        SortKey::ALL[(i + 1) % SortKey::ALL.len()]
+    }
+
+    // compares two items by the key, `Manual` treats all items as equal
+    // so a stable sort keeps the list order
+    pub fn compare(self, a: &TodoItem, b: &TodoItem) -> Ordering {
+        match self {
+            SortKey::Manual => Ordering::Equal,
+            SortKey::Status => status_position(a).cmp(&status_position(b)),
+            // highest priority first
+            SortKey::Priority => b.priority().cmp(&a.priority()),
+            // earliest due date first, items without a due date last
+            SortKey::Due => match (a.due(), b.due()) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            // newest first
+            SortKey::Created => b.created().cmp(&a.created()),
+            SortKey::Alphabetical => a.title().to_lowercase().cmp(&b.title().to_lowercase()),
+        }
+    }
+}
+
+impl GroupBy {
+    pub const ALL: [GroupBy; 3] = [GroupBy::None, GroupBy::Status, GroupBy::Tag];
+
+    // the name used in the data file and the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            GroupBy::None => "none",
+            GroupBy::Status => "status",
+            GroupBy::Tag => "tag",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            GroupBy::None => GroupBy::Status,
+            GroupBy::Status => GroupBy::Tag,
+            GroupBy::Tag => GroupBy::None,
+        }
+    }
+
+    // the labels of the groups the item belongs to, an item
+    // with several tags is shown under each of its tags
+    pub fn labels(self, item: &TodoItem) -> Vec<String> {
+        match self {
+            GroupBy::None => Vec::new(),
+            GroupBy::Status => vec![item.status().to_string()],
+            GroupBy::Tag if item.tags().is_empty() => vec![UNTAGGED_GROUP.to_string()],
+            GroupBy::Tag => item.tags().to_vec(),
+        }
+    }
+
+    // the order the group headers are shown in, statuses follow the
+    // workflow and tags are alphabetical with the untagged group last
+    pub fn compare_labels(self, a: &str, b: &str) -> Ordering {
+        match self {
+            GroupBy::None => Ordering::Equal,
+            GroupBy::Status => {
+                let workflow = Workflow::current();
+                let position = |name| workflow.position(name).unwrap_or(usize::MAX);
+                position(a).cmp(&position(b)).then_with(|| a.cmp(b))
+            }
+            GroupBy::Tag => (a == UNTAGGED_GROUP)
+                .cmp(&(b == UNTAGGED_GROUP))
+                .then_with(|| a.cmp(b)),
+        }
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SortKey::ALL
+            .into_iter()
+            .find(|key| key.name() == s)
+            .ok_or_else(|| format!("unknown sort key `{}`", s))
+    }
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GroupBy::ALL
+            .into_iter()
+            .find(|group| group.name() == s)
+            .ok_or_else(|| format!("unknown grouping `{}`", s))
+    }
+}
+
+// statuses that are not in the workflow are sorted last
+fn status_position(item: &TodoItem) -> usize {
+    Workflow::current()
+        .position(item.status().name())
+        .unwrap_or(usize::MAX)
+}
+
+impl std::fmt::Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortKey::Manual => write!(f, "manual"),
+            SortKey::Status => write!(f, "status"),
+            SortKey::Priority => write!(f, "priority"),
+            SortKey::Due => write!(f, "due date"),
+            SortKey::Created => write!(f, "created time"),
+            SortKey::Alphabetical => write!(f, "title"),
+        }
+    }
+}
+
+impl std::fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupBy::None => write!(f, "none"),
+            GroupBy::Status => write!(f, "status"),
+            GroupBy::Tag => write!(f, "tag"),
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+// how a task repeats, when a repeating task is done the next occurrence
+// is added with the due date moved forward, saved as the rule text
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Repeat {
+    Daily,
+    // on the given weekdays, on the weekday of the due date when empty
+    Weekly(Vec<Weekday>),
+    // on the day of the month, the last day of shorter months,
+    // on the day of the due date when not given
+    Monthly(Option<u32>),
+    // the number of days after the task was done
+    AfterDone(u32),
+}
+
+impl Repeat {
+    // the due date of the next occurrence of a task with the due date
+    // done today, occurrences are skipped until one is after today so
+    // an overdue task doesn't repeat in the past
+    pub fn next_due(&self, due: Option<NaiveDate>, today: NaiveDate) -> NaiveDate {
+        if let Repeat::AfterDone(days) = self {
+            return today + Days::new(*days as u64);
+        }
+
+        let start = due.unwrap_or(today);
+        let mut next = self.step(start, start);
+        while next <= today {
+            next = self.step(next, start);
+        }
+        next
+    }
+
+    // the first occurrence after the date, `start` is the due date
+    // the weekday or the day of the month is taken from when not given
+    fn step(&self, date: NaiveDate, start: NaiveDate) -> NaiveDate {
+        match self {
+            Repeat::Daily => date + Days::new(1),
+            Repeat::Weekly(days) if days.is_empty() => date + Days::new(7),
+            Repeat::Weekly(days) => (1..=7)
+                .map(|n| date + Days::new(n))
+                .find(|next| days.contains(&next.weekday()))
+                .unwrap_or(date + Days::new(7)),
+            Repeat::Monthly(day) => {
+                let day = day.unwrap_or(start.day());
+                let month = date.with_day(1).unwrap_or(date);
+                // the day of this month when it is still ahead, otherwise of the next
+                [month, month + Months::new(1)]
+                    .into_iter()
+                    .map(|month| day_of_month(month, day))
+                    .find(|next| *next > date)
+                    .unwrap_or(date + Months::new(1))
+            }
+            Repeat::AfterDone(days) => date + Days::new(*days as u64),
+        }
+    }
+}
+
+// the day of the month the date is in, clamped to the last day of the month
+fn day_of_month(month: NaiveDate, day: u32) -> NaiveDate {
+    (1..=day.clamp(1, 31))
+        .rev()
+        .find_map(|day| month.with_day(day))
+        .unwrap_or(month)
+}
+
+impl FromStr for Repeat {
+    type Err = String;
+
+    // `daily`, `weekly`, `weekly mon,thu`, `monthly`, `monthly 15` and
+    // `after 3 days`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        let (rule, args) = s.split_once(' ').unwrap_or((&s, ""));
+        let args = args.trim();
+        let invalid = || {
+            format!(
+                "invalid repeat `{}`, expected daily, weekly [mon,thu], monthly [15] or after N days",
+                s
+            )
+        };
+
+        match rule {
+            "daily" if args.is_empty() => Ok(Repeat::Daily),
+            "weekly" => {
+                let mut days = args
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|day| !day.is_empty())
+                    .map(|day| day.parse::<Weekday>().map_err(|_| invalid()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                days.sort_by_key(Weekday::num_days_from_monday);
+                days.dedup();
+                Ok(Repeat::Weekly(days))
+            }
+            "monthly" if args.is_empty() => Ok(Repeat::Monthly(None)),
+            "monthly" => match args.parse() {
+                Ok(day @ 1..=31) => Ok(Repeat::Monthly(Some(day))),
+                _ => Err(invalid()),
+            },
+            "after" => {
+                let days = args
+                    .trim_end_matches("days")
+                    .trim_end_matches("day")
+                    .trim_end_matches('d')
+                    .trim();
+                match days.parse() {
+                    Ok(days) if days > 0 => Ok(Repeat::AfterDone(days)),
+                    _ => Err(invalid()),
+                }
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl std::fmt::Display for Repeat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Repeat::Daily => write!(f, "daily"),
+            Repeat::Weekly(days) if days.is_empty() => write!(f, "weekly"),
+            Repeat::Weekly(days) => {
+                let days: Vec<String> = days
+                    .iter()
+                    .map(|day| day.to_string().to_lowercase())
+                    .collect();
+                write!(f, "weekly {}", days.join(","))
+            }
+            Repeat::Monthly(None) => write!(f, "monthly"),
+            Repeat::Monthly(Some(day)) => write!(f, "monthly {}", day),
+            Repeat::AfterDone(1) => write!(f, "after 1 day"),
+            Repeat::AfterDone(days) => write!(f, "after {} days", days),
+        }
+    }
+}
+
+impl From<Repeat> for String {
+    fn from(repeat: Repeat) -> Self {
+        repeat.to_string()
+    }
+}
+
+impl TryFrom<String> for Repeat {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn repeat(rule: &str) -> Repeat {
+        rule.parse().unwrap()
+    }
+
+    #[test]
+    fn monthly_clamps_to_short_months() {
+        let rule = repeat("monthly 31");
+        let due = date(2026, 1, 31);
+        assert_eq!(rule.next_due(Some(due), due), date(2026, 2, 28));
+        let due = date(2026, 2, 28);
+        assert_eq!(rule.next_due(Some(due), due), date(2026, 3, 31));
+        let due = date(2026, 3, 31);
+        assert_eq!(rule.next_due(Some(due), due), date(2026, 4, 30));
+    }
+
+    #[test]
+    fn monthly_uses_february_29_in_leap_years() {
+        let due = date(2028, 1, 31);
+        assert_eq!(
+            repeat("monthly 31").next_due(Some(due), due),
+            date(2028, 2, 29)
+        );
+        assert_eq!(
+            repeat("monthly").next_due(Some(due), due),
+            date(2028, 2, 29)
+        );
+        let due = date(2028, 1, 30);
+        assert_eq!(
+            repeat("monthly").next_due(Some(due), due),
+            date(2028, 2, 29)
+        );
+    }
+
+    #[test]
+    fn monthly_without_a_day_keeps_the_day_of_the_due_date() {
+        let due = date(2026, 1, 31);
+        assert_eq!(
+            repeat("monthly").next_due(Some(due), due),
+            date(2026, 2, 28)
+        );
+        let due = date(2026, 5, 15);
+        assert_eq!(
+            repeat("monthly").next_due(Some(due), due),
+            date(2026, 6, 15)
+        );
+    }
+
+    #[test]
+    fn weekly_days_cross_the_week_boundary() {
+        let rule = repeat("weekly mon,thu");
+        // a thursday repeats on the next monday
+        let due = date(2026, 10, 15);
+        assert_eq!(rule.next_due(Some(due), due), date(2026, 10, 19));
+        let due = date(2026, 10, 19);
+        assert_eq!(rule.next_due(Some(due), due), date(2026, 10, 22));
+        // across the end of the year
+        let due = date(2026, 12, 31);
+        assert_eq!(rule.next_due(Some(due), due), date(2027, 1, 4));
+    }
+
+    #[test]
+    fn weekly_without_days_keeps_the_weekday_of_the_due_date() {
+        let due = date(2026, 10, 16);
+        assert_eq!(
+            repeat("weekly").next_due(Some(due), due),
+            date(2026, 10, 23)
+        );
+    }
+
+    #[test]
+    fn overdue_tasks_repeat_after_today() {
+        let today = date(2026, 10, 18);
+        assert_eq!(
+            repeat("daily").next_due(Some(date(2026, 10, 10)), today),
+            date(2026, 10, 19)
+        );
+        assert_eq!(
+            repeat("weekly mon,thu").next_due(Some(date(2026, 10, 1)), today),
+            date(2026, 10, 19)
+        );
+        assert_eq!(
+            repeat("monthly 31").next_due(Some(date(2026, 8, 31)), today),
+            date(2026, 10, 31)
+        );
+    }
+
+    #[test]
+    fn tasks_without_a_due_date_repeat_from_today() {
+        let today = date(2026, 10, 18);
+        assert_eq!(repeat("daily").next_due(None, today), date(2026, 10, 19));
+        assert_eq!(repeat("weekly").next_due(None, today), date(2026, 10, 25));
+    }
+
+    #[test]
+    fn after_counts_from_the_day_the_task_was_done() {
+        let today = date(2026, 10, 18);
+        let rule = repeat("after 3 days");
+        assert_eq!(
+            rule.next_due(Some(date(2026, 10, 1)), today),
+            date(2026, 10, 21)
+        );
+        assert_eq!(
+            rule.next_due(Some(date(2026, 11, 1)), today),
+            date(2026, 10, 21)
+        );
+        assert_eq!(rule.next_due(None, today), date(2026, 10, 21));
+    }
+
+    #[test]
+    fn parses_the_rules() {
+        assert!(repeat("Daily") == Repeat::Daily);
+        assert!(repeat("weekly") == Repeat::Weekly(Vec::new()));
+        assert!(repeat("weekly thu, mon,mon") == Repeat::Weekly(vec![Weekday::Mon, Weekday::Thu]));
+        assert!(repeat("monthly") == Repeat::Monthly(None));
+        assert!(repeat("monthly 15") == Repeat::Monthly(Some(15)));
+        assert!(repeat("after 1 day") == Repeat::AfterDone(1));
+        assert!(repeat("after 3 days") == Repeat::AfterDone(3));
+        assert!(repeat("after 5d") == Repeat::AfterDone(5));
+        assert_eq!(repeat("weekly thu,mon").to_string(), "weekly mon,thu");
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        for rule in [
+            "",
+            "fortnightly",
+            "daily 2",
+            "weekly funday",
+            "monthly 0",
+            "monthly 32",
+            "monthly last",
+            "after 0 days",
+            "after days",
+        ] {
+            assert!(
+                rule.parse::<Repeat>().is_err(),
+                "`{}` should not parse",
+                rule
+            );
+        }
+    }
+}
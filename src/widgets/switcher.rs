@@ -0,0 +1,147 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Clear, List, ListItem, ListState, Widget},
+};
+
+use super::Input;
+use crate::theme::{Role, Theme};
+
+// what selecting a list in the switcher does
+#[derive(Clone, Copy, PartialEq)]
+pub enum SwitcherPurpose {
+    // makes the selected list the active list
+    Open,
+    // moves the selected task to the selected list
+    Move,
+}
+
+// popup listing the todo lists with their item counts,
+// a new list name can be entered in its input
+pub struct ListSwitcher {
+    purpose: SwitcherPurpose,
+    // names and item counts of the lists
+    lists: Vec<(String, usize)>,
+    active: String,
+    widget_state: ListState,
+    input: Input,
+    // true while the name of a new list is entered
+    creating: bool,
+}
+
+impl ListSwitcher {
+    // the active list is selected when opening a list, when moving
+    // a task the active list is left out since it already has the task
+    pub fn new(purpose: SwitcherPurpose, lists: Vec<(String, usize)>, active: &str) -> Self {
+        let lists: Vec<_> = match purpose {
+            SwitcherPurpose::Open => lists,
+            SwitcherPurpose::Move => lists.into_iter().filter(|(n, _)| n != active).collect(),
+        };
+        let selected = lists
+            .iter()
+            .position(|(name, _)| name == active)
+            .or((!lists.is_empty()).then_some(0));
+
+        Self {
+            purpose,
+            lists,
+            active: active.to_string(),
+            widget_state: ListState::default().with_selected(selected),
+            input: Input::new("new list name"),
+            creating: false,
+        }
+    }
+
+    pub fn purpose(&self) -> SwitcherPurpose {
+        self.purpose
+    }
+
+    pub fn selected(&self) -> Option<&str> {
+        let i = self.widget_state.selected()?;
+        self.lists.get(i).map(|(name, _)| name.as_str())
+    }
+
+    #[inline]
+    pub fn prev(&mut self) {
+        self.widget_state.select_previous();
+    }
+
+    #[inline]
+    pub fn next(&mut self) {
+        self.widget_state.select_next();
+    }
+
+    pub fn creating(&self) -> bool {
+        self.creating
+    }
+
+    // shows the input for the name of a new list, or hides it
+    pub fn set_creating(&mut self, creating: bool) {
+        self.creating = creating;
+        self.input.set_focused(creating);
+        if !creating {
+            self.input.clear();
+        }
+    }
+
+    pub fn input_mut(&mut self) -> &mut Input {
+        &mut self.input
+    }
+
+    pub fn new_list_name(&self) -> &str {
+        self.input.value().trim()
+    }
+}
+
+impl Widget for &mut ListSwitcher {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let theme = Theme::current();
+        let input_height = if self.creating { 3 } else { 0 };
+        let height = (self.lists.len() as u16 + 2 + input_height).max(4);
+        let [_, area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(48),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        let title = match self.purpose {
+            SwitcherPurpose::Open => " open list ",
+            SwitcherPurpose::Move => " move task to ",
+        };
+        let block = Block::bordered()
+            .title(title)
+            .border_style(theme.style(Role::FocusedBorder))
+            .style(theme.style(Role::Base));
+        let inner = block.inner(area);
+        Widget::render(Clear, area, buf);
+        block.render(area, buf);
+
+        let [list_area, input_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(input_height)]).areas(inner);
+
+        let items = self.lists.iter().map(|(name, count)| {
+            let marker = if *name == self.active { "* " } else { "  " };
+            ListItem::new(Line::from(vec![
+                Span::from(format!("{}{} ", marker, name)),
+                Span::styled(format!("({})", count), theme.style(Role::Accent)),
+            ]))
+        });
+        let list = List::new(items)
+            .highlight_symbol("> ")
+            .highlight_style(theme.style(Role::Selected));
+        StatefulWidget::render(list, list_area, buf, &mut self.widget_state);
+
+        if self.creating {
+            self.input.render(input_area, buf);
+        }
+    }
+}
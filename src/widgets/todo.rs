@@ -1,36 +1,157 @@
+use chrono::{DateTime, Local, NaiveDate};
 use ratatui::{
     prelude::*,
     widgets::{Block, List, ListItem, ListState, Widget},
 };
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use super::{
+    ordering::{GroupBy, ListSettings, SortKey},
+    CenteredText, Repeat,
+};
+use crate::{
+    fuzzy::{fuzzy_match, FuzzyMatch},
+    theme::{Role, Theme},
+    workflow::Workflow,
+};
+
+// format used to display the item timestamps
+pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M";
+// format used to display and parse due dates
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
 
-use super::CenteredText;
+// the name of a status from the configured `Workflow`
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TodoItemStatus(String);
 
-pub enum TodoItemStatus {
-    InProgress,
-    Complete,
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    #[default]
+    None,
+    Low,
+    Medium,
+    High,
 }
 
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct TodoItem {
+    // stable identifier of the item inside its list, assigned
+    // by `TodoList::add`, zero means the item wasn't assigned one yet
+    #[serde(default)]
+    id: u64,
     title: String,
     description: String,
     status: TodoItemStatus,
+    #[serde(default)]
+    priority: Priority,
+    #[serde(default)]
+    due: Option<NaiveDate>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default = "Local::now")]
+    created: DateTime<Local>,
+    #[serde(default = "Local::now")]
+    updated: DateTime<Local>,
+    #[serde(default)]
+    completed: Option<DateTime<Local>>,
+    // id of the item this item is a subtask of
+    #[serde(default)]
+    parent: Option<u64>,
+    // the rule the next occurrence is added by once the item is done
+    #[serde(default)]
+    repeat: Option<Repeat>,
+}
+
+// an item shown in the list after filtering, with the
+// char indexes of the filter matches in its title and tags
+#[derive(Clone)]
+struct VisibleItem {
+    index: usize,
+    // nesting level of the item in the subtask tree
+    depth: usize,
+    score: i64,
+    title_matches: Vec<usize>,
+    tag_matches: Vec<Vec<usize>>,
+}
+
+// a single line of the rendered list
+enum Row {
+    Header { label: String, count: usize },
+    Item(VisibleItem),
+}
+
+// the part of the list a click selected
+pub enum ListClick {
+    Header,
+    Item,
+    // the status prefix of an item row
+    Status,
+}
+
+// identifies the selected row so the selection can
+// follow it when the rows are recomputed
+#[derive(PartialEq)]
+enum RowKey {
+    Header(String),
+    Item(u64),
 }
 
 #[derive(Default)]
 pub struct TodoList {
     items: Vec<TodoItem>,
+    // the rows shown in the list, the widget
+    // selection is an index into this vector
+    rows: Vec<Row>,
+    filter: String,
+    // set once a task is moved while searching, the matching tasks are then
+    // shown in the manual order instead of ranked until the filter changes
+    keep_manual_order: bool,
+    settings: ListSettings,
     widget_state: ListState,
+    // the row the visual selection started at, the selection
+    // covers the rows between it and the selected row
+    visual_anchor: Option<usize>,
+    // ids of the items marked for bulk operations, not saved
+    marked: HashSet<u64>,
+    // where the rows were last rendered, used to find the clicked row
+    area: Rect,
+    // id given to the next added item, ids are never
+    // reused so they stay valid for scripts
+    next_id: u64,
+    // set whenever the items are mutated, used to know
+    // when the list needs to be written back to disk
+    changed: bool,
 }
 
 impl TodoItem {
     pub fn new(title: String, description: String) -> Self {
+        let now = Local::now();
         Self {
+            id: 0,
             title,
             description,
-            status: TodoItemStatus::InProgress,
+            status: TodoItemStatus::initial(),
+            priority: Priority::None,
+            due: None,
+            tags: Vec::new(),
+            created: now,
+            updated: now,
+            completed: None,
+            parent: None,
+            repeat: None,
         }
     }
 
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
     pub fn title(&self) -> &String {
         &self.title
     }
@@ -43,15 +164,224 @@ impl TodoItem {
         &self.status
     }
 
-    pub fn toggle_status(&mut self) {
-        match self.status {
-            TodoItemStatus::InProgress => self.status = TodoItemStatus::Complete,
-            TodoItemStatus::Complete => self.status = TodoItemStatus::InProgress,
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    pub fn due(&self) -> Option<NaiveDate> {
+        self.due
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn created(&self) -> DateTime<Local> {
+        self.created
+    }
+
+    pub fn updated(&self) -> DateTime<Local> {
+        self.updated
+    }
+
+    pub fn completed(&self) -> Option<DateTime<Local>> {
+        self.completed
+    }
+
+    pub fn parent(&self) -> Option<u64> {
+        self.parent
+    }
+
+    pub fn repeat(&self) -> Option<&Repeat> {
+        self.repeat.as_ref()
+    }
+
+    pub fn set_title(&mut self, title: String) {
+        self.title = title;
+        self.touch();
+    }
+
+    pub fn set_description(&mut self, description: String) {
+        self.description = description;
+        self.touch();
+    }
+
+    pub fn set_priority(&mut self, priority: Priority) {
+        self.priority = priority;
+        self.touch();
+    }
+
+    pub fn set_due(&mut self, due: Option<NaiveDate>) {
+        self.due = due;
+        self.touch();
+    }
+
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
+        self.touch();
+    }
+
+    pub fn set_repeat(&mut self, repeat: Option<Repeat>) {
+        self.repeat = repeat;
+        self.touch();
+    }
+
+    // imported items keep their id and parent to link the subtasks
+    // to their parents, the ids are replaced when added to a list
+    pub fn set_id(&mut self, id: u64) {
+        self.id = id;
+    }
+
+    pub fn set_parent(&mut self, parent: Option<u64>) {
+        self.parent = parent;
+    }
+
+    // restores the creation and completion times of an imported item,
+    // the completion time is only kept while the item is done
+    pub fn set_timestamps(&mut self, created: DateTime<Local>, completed: Option<DateTime<Local>>) {
+        self.created = created;
+        if self.is_done() {
+            self.completed = completed.or(self.completed);
+        }
+    }
+
+    // sets the status and keeps the completion time in sync with it
+    pub fn set_status(&mut self, status: TodoItemStatus) {
+        if !status.is_done() {
+            self.completed = None;
+        } else if !self.status.is_done() || self.completed.is_none() {
+            self.completed = Some(Local::now());
         }
+        self.status = status;
+        self.touch();
+    }
+
+    // moves the item to the next status of the workflow,
+    // or to the previous one when `forward` is false
+    pub fn cycle_status(&mut self, forward: bool) {
+        let workflow = Workflow::current();
+        let next = if forward {
+            workflow.next(self.status.name())
+        } else {
+            workflow.prev(self.status.name())
+        };
+        self.set_status(TodoItemStatus::from(next));
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.status.is_done()
+    }
+
+    // unfinished items are overdue the day after they are due
+    pub fn is_overdue(&self) -> bool {
+        !self.is_done() && self.due.is_some_and(|due| due < Local::now().date_naive())
+    }
+
+    #[inline]
+    fn touch(&mut self) {
+        self.updated = Local::now();
     }
 }
 
 impl TodoList {
+    pub fn new(items: Vec<TodoItem>, next_id: u64) -> Self {
+        let max_id = items.iter().map(|item| item.id).max().unwrap_or(0);
+        let mut list = Self {
+            items,
+            next_id: next_id.max(max_id + 1),
+            ..Default::default()
+        };
+
+        // items saved before ids existed are given one
+        for item in list.items.iter_mut().filter(|item| item.id == 0) {
+            item.id = list.next_id;
+            list.next_id += 1;
+        }
+        list.refresh_rows();
+        list
+    }
+
+    pub fn next_id(&self) -> u64 {
+        self.next_id
+    }
+
+    pub fn items(&self) -> &[TodoItem] {
+        &self.items
+    }
+
+    // replaces all the items, used to restore a previous state of
+    // the list, the selection stays on the same item if it still exists
+    pub fn set_items(&mut self, items: Vec<TodoItem>) {
+        let selected = self.selected_key();
+        self.items = items;
+        self.changed = true;
+        let ids: HashSet<u64> = self.items.iter().map(|item| item.id).collect();
+        self.marked.retain(|id| ids.contains(id));
+        self.refresh_rows_keeping(selected);
+    }
+
+    // returns if the list was changed since the last call
+    // and resets the flag
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    // marks the list as changed so it is written to disk
+    pub fn mark_changed(&mut self) {
+        self.changed = true;
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    // shows only the items fuzzy matching the filter
+    // by title, description or tags
+    pub fn set_filter(&mut self, filter: &str) {
+        self.filter = filter.trim().to_string();
+        self.keep_manual_order = false;
+        self.refresh_rows();
+    }
+
+    pub fn settings(&self) -> &ListSettings {
+        &self.settings
+    }
+
+    pub fn set_settings(&mut self, settings: ListSettings) {
+        self.settings = settings;
+        self.refresh_rows();
+    }
+
+    // switches to the next sort key and returns it
+    pub fn cycle_sort(&mut self) -> SortKey {
+        self.set_sort(self.settings.sort.next());
+        self.settings.sort
+    }
+
+    // switches to the next grouping and returns it
+    pub fn cycle_group(&mut self) -> GroupBy {
+        self.set_group(self.settings.group.next());
+        self.settings.group
+    }
+
+    // collapses or expands the group under the selected header,
+    // returns false if the selected row is not a header
+    pub fn toggle_current_group(&mut self) -> bool {
+        let Some(label) = self.selected_group().map(String::from) else {
+            return false;
+        };
+
+        match self.settings.collapsed.iter().position(|l| *l == label) {
+            Some(i) => {
+                self.settings.collapsed.remove(i);
+            }
+            None => self.settings.collapsed.push(label),
+        }
+        self.changed = true;
+        self.refresh_rows();
+        true
+    }
+
     pub fn selected(&self) -> Option<&TodoItem> {
         if let Some(i) = self.selected_index() {
             return Some(&self.items[i]);
@@ -59,6 +389,41 @@ impl TodoList {
         None
     }
 
+    // the label of the selected group header
+    pub fn selected_group(&self) -> Option<&str> {
+        match self.widget_state.selected().and_then(|i| self.rows.get(i)) {
+            Some(Row::Header { label, .. }) => Some(label),
+            _ => None,
+        }
+    }
+
+    // selects the row rendered at the position, returns
+    // what was clicked or `None` if the position is not on a row
+    pub fn select_at(&mut self, position: Position) -> Option<ListClick> {
+        if !self.area.contains(position) {
+            return None;
+        }
+        let index = self.widget_state.offset() + (position.y - self.area.y) as usize;
+        let row = self.rows.get(index)?;
+
+        // the status follows the highlight symbol, which
+        // is only drawn while a row is selected
+        let symbol_width = if self.widget_state.selected().is_some() {
+            2
+        } else {
+            0
+        };
+        let status_start = self.area.x + symbol_width;
+        let status_end = status_start + Workflow::current().name_width() as u16;
+        let click = match row {
+            Row::Header { .. } => ListClick::Header,
+            Row::Item(_) if (status_start..status_end).contains(&position.x) => ListClick::Status,
+            Row::Item(_) => ListClick::Item,
+        };
+        self.widget_state.select(Some(index));
+        Some(click)
+    }
+
     #[inline]
     pub fn prev(&mut self) {
         self.widget_state.select_previous();
@@ -69,22 +434,448 @@ impl TodoList {
         self.widget_state.select_next();
     }
 
-    #[inline]
-    pub fn add(&mut self, item: TodoItem) {
+    // moves the selection by `offset` rows, stopping at the first and last row
+    pub fn select_relative(&mut self, offset: isize) {
+        let Some(last) = self.rows.len().checked_sub(1) else {
+            return;
+        };
+        let row = match self.widget_state.selected() {
+            Some(i) => i.min(last).saturating_add_signed(offset).min(last),
+            None if offset > 0 => last.min(offset as usize - 1),
+            None => last.saturating_sub(offset.unsigned_abs().saturating_sub(1)),
+        };
+        self.widget_state.select(Some(row));
+    }
+
+    // selects the row at the index, or the last row if there are fewer rows
+    pub fn select_row(&mut self, row: usize) {
+        if let Some(last) = self.rows.len().checked_sub(1) {
+            self.widget_state.select(Some(row.min(last)));
+        }
+    }
+
+    // starts selecting the rows between the selected row and the
+    // rows the selection is moved to, returns false if no row is selected
+    pub fn start_visual(&mut self) -> bool {
+        self.visual_anchor = self.widget_state.selected();
+        self.visual_anchor.is_some()
+    }
+
+    pub fn stop_visual(&mut self) {
+        self.visual_anchor = None;
+    }
+
+    // the ids of the items an operation applies to, the visually selected
+    // items, the marked items or the items of `count` rows starting at the
+    // selected row, in that order of preference
+    pub fn target_ids(&self, count: usize) -> Vec<u64> {
+        if self.visual_anchor.is_none() && !self.marked.is_empty() {
+            return self
+                .items
+                .iter()
+                .map(|item| item.id)
+                .filter(|id| self.marked.contains(id))
+                .collect();
+        }
+        self.range_ids(count)
+    }
+
+    // marks the items of the visual selection or of `count` rows starting at
+    // the selected row, they are unmarked instead if all of them were marked
+    pub fn toggle_marks(&mut self, count: usize) {
+        let ids = self.range_ids(count);
+        if ids.iter().all(|id| self.marked.contains(id)) {
+            for id in &ids {
+                self.marked.remove(id);
+            }
+        } else {
+            self.marked.extend(ids);
+        }
+    }
+
+    // marks every item, including the ones hidden by the filter
+    pub fn mark_all(&mut self) {
+        self.marked = self.items.iter().map(|item| item.id).collect();
+    }
+
+    // marks the items matching the filter, including the
+    // ones in collapsed groups and folded subtasks
+    pub fn mark_matching(&mut self) {
+        let matching = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(index, item)| TodoList::match_item(&self.filter, item, *index).is_some())
+            .map(|(_, item)| item.id);
+        self.marked.extend(matching);
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+    }
+
+    pub fn marked_count(&self) -> usize {
+        self.marked.len()
+    }
+
+    // ids of the items in the visual selection or in `count` rows
+    // starting at the selected row, header rows are skipped
+    fn range_ids(&self, count: usize) -> Vec<u64> {
+        let Some(selected) = self.widget_state.selected() else {
+            return Vec::new();
+        };
+        let (start, end) = match self.visual_anchor {
+            Some(anchor) => (anchor.min(selected), anchor.max(selected)),
+            None => (selected, selected.saturating_add(count.max(1) - 1)),
+        };
+        self.rows
+            .iter()
+            .skip(start)
+            .take(end - start + 1)
+            .filter_map(|row| match row {
+                Row::Item(visible) => Some(self.items[visible.index].id),
+                Row::Header { .. } => None,
+            })
+            .collect()
+    }
+
+    // removes the items with their subtasks, returns the removed
+    // items without the subtasks
+    pub fn remove_items(&mut self, ids: &[u64]) -> Vec<TodoItem> {
+        self.remove_trees(ids)
+            .into_iter()
+            .filter_map(|tree| tree.into_iter().next())
+            .collect()
+    }
+
+    // removes the items with their subtasks like `remove_tree`, an item
+    // whose parent is also removed stays in the tree of its parent
+    pub fn remove_trees(&mut self, ids: &[u64]) -> Vec<Vec<TodoItem>> {
+        let removed: HashSet<u64> = ids.iter().copied().collect();
+        let roots: Vec<u64> = ids
+            .iter()
+            .copied()
+            .filter(|id| !self.has_ancestor_in(*id, &removed))
+            .collect();
+        roots
+            .into_iter()
+            .map(|id| self.remove_tree(id))
+            .filter(|tree| !tree.is_empty())
+            .collect()
+    }
+
+    // moves the selected item past `steps` of its siblings, down for positive
+    // steps and up for negative ones, stopping at the first or last sibling,
+    // the siblings are the shown items with the same parent under the same
+    // group header, items hidden by the filter keep their place between
+    // them, fails when the list is not in the manual order, while searching
+    // the matching tasks stop being ranked so they are moved among
+    // their neighbours in the manual order
+    pub fn move_current(&mut self, steps: isize) -> Result<(), String> {
+        if self.settings.sort != SortKey::Manual {
+            return Err(format!(
+                "tasks can't be moved while sorted by {}, sort them manually first",
+                self.settings.sort
+            ));
+        }
+        if self.ranks_matches() {
+            self.keep_manual_order = true;
+            let id = self.selected().map(|item| item.id);
+            self.refresh_rows_keeping(id.map(RowKey::Item));
+        }
+        let Some(selected) = self.widget_state.selected() else {
+            return Ok(());
+        };
+        let Some(Row::Item(current)) = self.rows.get(selected) else {
+            return Ok(());
+        };
+        let (depth, id) = (current.depth, self.items[current.index].id);
+
+        // the rows in the direction of the move, nearest first
+        let rows: Vec<usize> = if steps > 0 {
+            (selected + 1..self.rows.len()).collect()
+        } else {
+            (0..selected).rev().collect()
+        };
+        let mut target = None;
+        let mut passed = 0;
+        for row in rows {
+            match &self.rows[row] {
+                Row::Item(visible) if visible.depth == depth => {
+                    target = Some(self.items[visible.index].id);
+                    passed += 1;
+                    if passed == steps.unsigned_abs() {
+                        break;
+                    }
+                }
+                // deeper rows are subtasks of the siblings
+                Row::Item(visible) if visible.depth > depth => {}
+                _ => break,
+            }
+        }
+        let Some(target) = target else {
+            return Ok(());
+        };
+
+        let index = self.items.iter().position(|item| item.id == id);
+        let item = self.items.remove(index.expect("the selected item exists"));
+        let target = self.items.iter().position(|item| item.id == target);
+        let target = target.expect("the target item exists");
+        let position = if steps > 0 { target + 1 } else { target };
+        self.items.insert(position, item);
+        self.changed = true;
+        self.refresh_rows_keeping(Some(RowKey::Item(id)));
+        Ok(())
+    }
+
+    // adds the tags in `add` to the items and removes the tags in `remove`
+    pub fn retag(&mut self, ids: &[u64], add: &[String], remove: &[String]) {
+        for item in self.items.iter_mut().filter(|item| ids.contains(&item.id)) {
+            let mut tags: Vec<String> = item
+                .tags
+                .iter()
+                .filter(|tag| !remove.contains(tag))
+                .cloned()
+                .collect();
+            for tag in add {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+            if tags != item.tags {
+                item.set_tags(tags);
+                self.changed = true;
+            }
+        }
+        self.refresh_rows();
+    }
+
+    // moves the items to the next status, or the previous one when
+    // `forward` is false
+    pub fn cycle_status_of(&mut self, ids: &[u64], forward: bool) {
+        for item in self.items.iter_mut().filter(|item| ids.contains(&item.id)) {
+            item.cycle_status(forward);
+            self.changed = true;
+        }
+        self.repeat_done(ids);
+    }
+
+    // sets the status of the item, returns false if there is no such item
+    pub fn set_status_of(&mut self, id: u64, status: TodoItemStatus) -> bool {
+        let Some(item) = self.get_mut(id) else {
+            return false;
+        };
+        item.set_status(status);
+        self.repeat_done(&[id]);
+        true
+    }
+
+    // adds the next occurrence of the repeating items that are done, the
+    // rule moves to the new item so the done item doesn't repeat again
+    // when its status changes, the occurrence is added after the item
+    fn repeat_done(&mut self, ids: &[u64]) {
+        let today = Local::now().date_naive();
+        let mut added = false;
+        let mut i = 0;
+        while i < self.items.len() {
+            let item = &mut self.items[i];
+            i += 1;
+            if !ids.contains(&item.id) || !item.is_done() {
+                continue;
+            }
+            let Some(repeat) = item.repeat.take() else {
+                continue;
+            };
+
+            let mut next = TodoItem::new(item.title.clone(), item.description.clone());
+            next.priority = item.priority;
+            next.tags = item.tags.clone();
+            next.parent = item.parent;
+            next.due = Some(repeat.next_due(item.due, today));
+            next.repeat = Some(repeat);
+            next.id = self.next_id.max(1);
+            self.next_id = next.id + 1;
+            self.items.insert(i, next);
+            i += 1;
+            added = true;
+        }
+        if added {
+            self.changed = true;
+            self.refresh_rows();
+        }
+    }
+
+    pub fn set_sort(&mut self, sort: SortKey) {
+        self.settings.sort = sort;
+        self.changed = true;
+        self.refresh_rows();
+    }
+
+    pub fn set_group(&mut self, group: GroupBy) {
+        self.settings.group = group;
+        self.settings.collapsed.clear();
+        self.changed = true;
+        self.refresh_rows();
+    }
+
+    // the tags used by the items sorted by name, for completion
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.items.iter().flat_map(|i| i.tags.clone()).collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    // adds the item to the end of the list and assigns
+    // it a new id, the assigned id is returned
+    pub fn add(&mut self, mut item: TodoItem) -> u64 {
+        let id = self.next_id.max(1);
+        self.next_id = id + 1;
+        item.id = id;
         self.items.push(item);
+        self.changed = true;
+        self.refresh_rows();
+        id
     }
 
-    pub fn delete_current(&mut self) -> Option<TodoItem> {
-        if let Some(i) = self.selected_index() {
-            return Some(self.items.remove(i));
+    // adds the item as a subtask of the item with the `parent` id, the
+    // parent is unfolded so the subtask is shown, returns `None` without
+    // adding it if there is no such item
+    pub fn add_child(&mut self, parent: u64, mut item: TodoItem) -> Option<u64> {
+        self.get(parent)?;
+        self.settings.folded.retain(|id| *id != parent);
+        item.parent = Some(parent);
+        Some(self.add(item))
+    }
+
+    pub fn get(&self, id: u64) -> Option<&TodoItem> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut TodoItem> {
+        let item = self.items.iter_mut().find(|item| item.id == id)?;
+        self.changed = true;
+        Some(item)
+    }
+
+    // removes the item together with all of its subtasks
+    pub fn remove(&mut self, id: u64) -> Option<TodoItem> {
+        self.remove_tree(id).into_iter().next()
+    }
+
+    // removes the item and all of its subtasks and returns them, the
+    // removed item comes first, empty if there is no item with the id
+    pub fn remove_tree(&mut self, id: u64) -> Vec<TodoItem> {
+        if self.get(id).is_none() {
+            return Vec::new();
+        }
+        self.changed = true;
+        let selected = self.selected_key();
+        let descendants = self.descendants(id);
+        let (mut removed, items): (Vec<_>, Vec<_>) = std::mem::take(&mut self.items)
+            .into_iter()
+            .partition(|item| item.id == id || descendants.contains(&item.id));
+        removed.sort_by_key(|item| item.id != id);
+        for item in &removed {
+            self.marked.remove(&item.id);
+        }
+        self.items = items;
+        self.refresh_rows_keeping(selected);
+        removed
+    }
+
+    // adds items removed from another list with `remove_tree`, the items
+    // are given new ids and keep their subtask structure, the first item
+    // is added at the top level, returns its new id
+    pub fn add_tree(&mut self, mut items: Vec<TodoItem>) -> Option<u64> {
+        if let Some(root) = items.first_mut() {
+            root.parent = None;
+        }
+        self.add_items(items).first().copied()
+    }
+
+    // adds items from outside the list, the items are given new ids and
+    // keep their subtask structure, parents that are not among the items
+    // are dropped, returns the new ids in the order of the items
+    pub fn add_items(&mut self, items: Vec<TodoItem>) -> Vec<u64> {
+        let first_id = self.next_id.max(1);
+        let new_ids: Vec<u64> = (first_id..first_id + items.len() as u64).collect();
+        // when items share an id the first one keeps the subtasks
+        let mut ids: HashMap<u64, u64> = HashMap::new();
+        for (item, id) in items.iter().zip(&new_ids) {
+            ids.entry(item.id).or_insert(*id);
+        }
+        self.next_id = first_id + items.len() as u64;
+
+        for (mut item, id) in items.into_iter().zip(&new_ids) {
+            item.id = *id;
+            item.parent = item
+                .parent
+                .and_then(|parent| ids.get(&parent).copied())
+                .filter(|parent| *parent != item.id);
+            self.items.push(item);
+        }
+        self.changed = true;
+        self.refresh_rows();
+        new_ids
+    }
+
+    // the number of finished and total direct subtasks of the item
+    pub fn progress(&self, id: u64) -> (usize, usize) {
+        let children = self.items.iter().filter(|item| item.parent == Some(id));
+        children.fold((0, 0), |(done, total), item| {
+            (done + item.is_done() as usize, total + 1)
+        })
+    }
+
+    // folds the subtasks of the selected item, when the item has no
+    // subtasks or is already folded the selection moves to its parent
+    pub fn fold_current(&mut self) {
+        let Some(i) = self.selected_index() else {
+            return;
+        };
+        let (id, parent) = (self.items[i].id, self.items[i].parent);
+
+        if self.has_children(id) && !self.settings.folded.contains(&id) {
+            self.settings.folded.push(id);
+            self.changed = true;
+            self.refresh_rows();
+        } else if let Some(parent) = parent {
+            self.select_item(parent);
+        }
+    }
+
+    // unfolds the subtasks of the selected item, when the item is
+    // already unfolded the selection moves to its first subtask
+    pub fn unfold_current(&mut self) {
+        let Some(i) = self.selected_index() else {
+            return;
+        };
+        let id = self.items[i].id;
+
+        match self.settings.folded.iter().position(|f| *f == id) {
+            Some(i) => {
+                self.settings.folded.remove(i);
+                self.changed = true;
+                self.refresh_rows();
+            }
+            None if self.has_children(id) => self.next(),
+            None => {}
         }
-        None
     }
 
-    pub fn toggle_current_status(&mut self) {
+    pub fn delete_current(&mut self) -> Option<TodoItem> {
+        let id = self.selected()?.id;
+        self.remove(id)
+    }
+
+    pub fn cycle_current_status(&mut self, forward: bool) {
         if let Some(i) = self.selected_index() {
             let item = &mut self.items[i];
-            item.toggle_status();
+            item.cycle_status(forward);
+            let id = item.id;
+            self.changed = true;
+            self.repeat_done(&[id]);
         }
     }
 
@@ -93,50 +884,437 @@ impl TodoList {
         self.items.is_empty()
     }
 
+    fn has_children(&self, id: u64) -> bool {
+        self.items.iter().any(|item| item.parent == Some(id))
+    }
+
+    // if the parent of the item, or a parent of that, is one of the ids
+    fn has_ancestor_in(&self, id: u64, ids: &HashSet<u64>) -> bool {
+        let mut seen = HashSet::new();
+        let mut parent = self.get(id).and_then(|item| item.parent);
+        while let Some(id) = parent {
+            if ids.contains(&id) {
+                return true;
+            }
+            // parent cycles would loop forever
+            if !seen.insert(id) {
+                return false;
+            }
+            parent = self.get(id).and_then(|item| item.parent);
+        }
+        false
+    }
+
+    // ids of the subtasks of the item, nested to any depth
+    fn descendants(&self, id: u64) -> HashSet<u64> {
+        let mut descendants = HashSet::new();
+        let mut pending = vec![id];
+        while let Some(parent) = pending.pop() {
+            for item in &self.items {
+                if item.parent == Some(parent) && descendants.insert(item.id) {
+                    pending.push(item.id);
+                }
+            }
+        }
+        descendants
+    }
+
+    // selects the row of the item if it is shown
+    fn select_item(&mut self, id: u64) {
+        let position = self.rows.iter().position(|row| match row {
+            Row::Item(visible) => self.items[visible.index].id == id,
+            _ => false,
+        });
+        if position.is_some() {
+            self.widget_state.select(position);
+        }
+    }
+
+    // index into `items` of the selected item
     fn selected_index(&self) -> Option<usize> {
-        match self.widget_state.selected() {
-            Some(i) if !self.is_empty() && i < self.items.len() => Some(i),
+        match self.widget_state.selected().and_then(|i| self.rows.get(i)) {
+            Some(Row::Item(visible)) => Some(visible.index),
             _ => None,
         }
     }
 
+    fn selected_key(&self) -> Option<RowKey> {
+        match self
+            .widget_state
+            .selected()
+            .and_then(|i| self.rows.get(i))?
+        {
+            Row::Header { label, .. } => Some(RowKey::Header(label.clone())),
+            Row::Item(visible) => Some(RowKey::Item(self.items[visible.index].id)),
+        }
+    }
+
+    fn refresh_rows(&mut self) {
+        let selected = self.selected_key();
+        self.refresh_rows_keeping(selected);
+    }
+
+    // recomputes the rows from the filter and list settings, the selection
+    // stays on the `selected` row if it is still shown, otherwise it is
+    // kept in place or moved to the first row when filtering
+    fn refresh_rows_keeping(&mut self, selected: Option<RowKey>) {
+        let previous = self.widget_state.selected();
+
+        let mut visible: Vec<VisibleItem> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| TodoList::match_item(&self.filter, item, index))
+            .collect();
+
+        // the parents of matching subtasks are shown to keep the tree intact
+        if !self.filter.is_empty() {
+            let mut shown: HashSet<usize> = visible.iter().map(|v| v.index).collect();
+            let ids: HashMap<u64, usize> = self
+                .items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| (item.id, index))
+                .collect();
+            let mut pending: Vec<usize> = shown.iter().copied().collect();
+            while let Some(index) = pending.pop() {
+                let parent = self.items[index].parent.and_then(|id| ids.get(&id));
+                if let Some(&parent) = parent {
+                    if shown.insert(parent) {
+                        pending.push(parent);
+                        visible.push(VisibleItem::unmatched(parent, &self.items[parent]));
+                    }
+                }
+            }
+        }
+
+        // when filtering without a sort key the best matches are shown first,
+        // the sorts are stable so equal items keep the list order
+        let sort = self.settings.sort;
+        if self.ranks_matches() {
+            visible.sort_by_key(|visible| std::cmp::Reverse(visible.score));
+        } else {
+            visible.sort_by(|a, b| sort.compare(&self.items[a.index], &self.items[b.index]));
+        }
+
+        self.rows = self.group_rows(visible);
+
+        let position = selected.and_then(|key| {
+            self.rows.iter().position(|row| match (row, &key) {
+                (Row::Header { label, .. }, RowKey::Header(selected)) => label == selected,
+                (Row::Item(visible), RowKey::Item(id)) => self.items[visible.index].id == *id,
+                _ => false,
+            })
+        });
+        let selection = match (position, previous) {
+            _ if self.rows.is_empty() => None,
+            (Some(i), _) => Some(i),
+            (None, Some(i)) => Some(i.min(self.rows.len() - 1)),
+            (None, None) if !self.filter.is_empty() => Some(0),
+            (None, None) => None,
+        };
+        self.widget_state.select(selection);
+    }
+
+    // places the sorted items under their group headers, the items
+    // of collapsed groups are left out but still counted in the header
+    fn group_rows(&self, visible: Vec<VisibleItem>) -> Vec<Row> {
+        let group = self.settings.group;
+        if group == GroupBy::None {
+            return self.tree_rows(visible);
+        }
+
+        let mut groups: Vec<(String, Vec<VisibleItem>)> = Vec::new();
+        for visible in visible {
+            for label in group.labels(&self.items[visible.index]) {
+                match groups.iter_mut().find(|(l, _)| *l == label) {
+                    Some((_, items)) => items.push(visible.clone()),
+                    None => groups.push((label, vec![visible.clone()])),
+                }
+            }
+        }
+        groups.sort_by(|(a, _), (b, _)| group.compare_labels(a, b));
+
+        let mut rows = Vec::new();
+        for (label, items) in groups {
+            let collapsed = self.settings.collapsed.contains(&label);
+            rows.push(Row::Header {
+                label,
+                count: items.len(),
+            });
+            if !collapsed {
+                rows.extend(self.tree_rows(items));
+            }
+        }
+        rows
+    }
+
+    // orders the sorted items as a tree, subtasks follow their parent
+    // keeping their sorted order, items whose parent is not among the
+    // items are roots, the subtasks of folded items are left out
+    // unless filtering so matching subtasks are always shown
+    fn tree_rows(&self, visible: Vec<VisibleItem>) -> Vec<Row> {
+        let ids: HashSet<u64> = visible.iter().map(|v| self.items[v.index].id).collect();
+        let mut roots = Vec::new();
+        let mut children: HashMap<u64, Vec<VisibleItem>> = HashMap::new();
+        for visible in visible {
+            match self.items[visible.index].parent {
+                Some(parent) if ids.contains(&parent) => {
+                    children.entry(parent).or_default().push(visible)
+                }
+                _ => roots.push(visible),
+            }
+        }
+
+        // the items are walked depth first, the subtasks of folded
+        // items are walked as hidden so they don't appear as roots
+        let mut rows = Vec::new();
+        let mut pending: Vec<(VisibleItem, bool)> =
+            roots.into_iter().rev().map(|v| (v, false)).collect();
+        loop {
+            if pending.is_empty() {
+                // items in a parent cycle are never reached from a root,
+                // they are shown at the top level instead of being lost
+                let Some(&cycle) = children.keys().next() else {
+                    break;
+                };
+                let items = children.remove(&cycle).unwrap_or_default();
+                pending.extend(items.into_iter().rev().map(|v| (v, false)));
+                continue;
+            }
+
+            let (visible, hidden) = pending.pop().expect("pending is not empty");
+            let id = self.items[visible.index].id;
+            let folded = self.filter.is_empty() && self.settings.folded.contains(&id);
+            if let Some(items) = children.remove(&id) {
+                let depth = visible.depth + 1;
+                pending.extend(items.into_iter().rev().map(|mut child| {
+                    child.depth = depth;
+                    (child, hidden || folded)
+                }));
+            }
+            if !hidden {
+                rows.push(Row::Item(visible));
+            }
+        }
+        rows
+    }
+
+    // true when the rows are ordered by how well they match the filter
+    // instead of by the manual order
+    fn ranks_matches(&self) -> bool {
+        self.settings.sort == SortKey::Manual && !self.filter.is_empty() && !self.keep_manual_order
+    }
+
+    // the item has to have the tag of every `tag:` term and the status of
+    // every `status:` term of the filter, spaces in status names are written
+    // as `-`, the rest of the filter is fuzzy matched
+    fn match_item(filter: &str, item: &TodoItem, index: usize) -> Option<VisibleItem> {
+        let mut text = Vec::new();
+        for term in filter.split_whitespace() {
+            match term.split_once(':') {
+                Some(("tag" | "status", "")) => {}
+                Some(("tag", tag)) => {
+                    if !item.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                        return None;
+                    }
+                }
+                Some(("status", status)) => {
+                    let name = item.status.name().replace(' ', "-");
+                    if !name.eq_ignore_ascii_case(status) {
+                        return None;
+                    }
+                }
+                _ => text.push(term),
+            }
+        }
+        let filter = text.join(" ");
+        let filter = filter.as_str();
+
+        let title = fuzzy_match(filter, &item.title);
+        let description = fuzzy_match(filter, &item.description);
+        let tags: Vec<_> = item
+            .tags
+            .iter()
+            .map(|tag| fuzzy_match(filter, tag))
+            .collect();
+
+        let score = [&title, &description]
+            .into_iter()
+            .chain(&tags)
+            .flatten()
+            .map(|m| m.score)
+            .max()?;
+
+        // an empty filter matches everything without anything to highlight
+        let indices = |m: Option<FuzzyMatch>| match m {
+            Some(m) if !filter.is_empty() => m.indices,
+            _ => Vec::new(),
+        };
+        Some(VisibleItem {
+            index,
+            depth: 0,
+            score,
+            title_matches: indices(title),
+            tag_matches: tags.into_iter().map(indices).collect(),
+        })
+    }
+
     fn render_empty_todo_list(&self, area: Rect, buf: &mut Buffer) {
-        CenteredText::new(Text::from("Todo list empty").cyan())
-            .block(Block::default().white().on_black())
+        let text = if self.is_empty() {
+            "Todo list empty".to_string()
+        } else {
+            format!("No tasks match `{}`", self.filter)
+        };
+
+        let theme = Theme::current();
+        CenteredText::new(Text::from(text).style(theme.style(Role::Hint)))
+            .block(Block::default().style(theme.style(Role::Base)))
             .render(area, buf);
     }
 
     fn render_todo_list(&mut self, area: Rect, buf: &mut Buffer) {
-        let items = self.items.iter().enumerate().map(|(i, item)| {
-            let prefix = match item.status() {
-                TodoItemStatus::InProgress => {
-                    Span::styled("in progress", Style::default().light_blue())
-                }
-                TodoItemStatus::Complete => {
-                    Span::styled("complete   ", Style::default().light_green())
+        let workflow = Workflow::current();
+        let theme = Theme::current();
+        let status_width = workflow.name_width();
+        let progress: HashMap<u64, (usize, usize)> = self
+            .items
+            .iter()
+            .filter_map(|item| item.parent)
+            .collect::<HashSet<u64>>()
+            .into_iter()
+            .map(|id| (id, self.progress(id)))
+            .collect();
+        let visual = self
+            .visual_anchor
+            .zip(self.widget_state.selected())
+            .map(|(anchor, selected)| anchor.min(selected)..=anchor.max(selected));
+
+        let items = self.rows.iter().enumerate().map(|(i, row)| {
+            let visible = match row {
+                Row::Header { label, count } => {
+                    let collapsed = self.settings.collapsed.contains(label);
+                    return TodoList::header_list_item(label, *count, collapsed);
                 }
+                Row::Item(visible) => visible,
+            };
+            let item = &self.items[visible.index];
+            let status = item.status().name();
+            let status_style = workflow.style(status);
+            let prefix = Span::styled(
+                format!("{:<width$}", status, width = status_width),
+                status_style,
+            );
+
+            // subtasks are indented under their parent, parents are
+            // marked as folded or unfolded
+            let progress = progress.get(&item.id());
+            let marker = match progress {
+                Some(_) if self.settings.folded.contains(&item.id()) => "▸ ",
+                Some(_) => "▾ ",
+                None if visible.depth > 0 => "· ",
+                None => "",
             };
 
-            let item_line = Line::from(vec![
+            // marked items have a marker in place of the separator
+            let separator = if self.marked.contains(&item.id()) {
+                Span::styled(" ● ", theme.style(Role::Marked))
+            } else {
+                Span::from(" | ")
+            };
+            let mut spans = vec![
                 prefix,
-                Span::from(" | "),
-                Span::from(format!("(#{}) ", i.to_string())).light_cyan(),
-                Span::from(item.title()),
-            ]);
-            ListItem::new(item_line).style(TodoList::alternate_color(i))
+                separator,
+                Span::from(format!("{}{}", "  ".repeat(visible.depth), marker)),
+                Span::styled(format!("(#{}) ", item.id()), theme.style(Role::Accent)),
+            ];
+            spans.extend(highlight_matches(
+                item.title(),
+                &visible.title_matches,
+                Style::default(),
+            ));
+            if let Some((done, total)) = progress {
+                spans.push(Span::styled(
+                    format!(" {}/{} complete", done, total),
+                    theme.style(Role::Hint),
+                ));
+            }
+            spans.extend(TodoList::metadata_spans(item, &visible.tag_matches));
+
+            let item_line = Line::from(spans);
+            let style = match &visual {
+                Some(rows) if rows.contains(&i) => theme.style(Role::Visual),
+                _ if item.is_overdue() => {
+                    TodoList::alternate_color(i).patch(theme.style(Role::Overdue))
+                }
+                _ => TodoList::alternate_color(i),
+            };
+            ListItem::new(item_line).style(style)
         });
         let list = List::new(items)
             .highlight_symbol("> ")
-            .highlight_style(Style::default().black().on_white().bold())
-            .block(Block::default().white().on_black());
+            .highlight_style(theme.style(Role::Selected))
+            .block(Block::default().style(theme.style(Role::Base)));
+        self.area = area;
         StatefulWidget::render(list, area, buf, &mut self.widget_state);
     }
 
+    fn header_list_item(label: &str, count: usize, collapsed: bool) -> ListItem<'static> {
+        let theme = Theme::current();
+        let marker = if collapsed { "▸" } else { "▾" };
+
+        ListItem::new(Line::from(vec![
+            Span::from(format!("{} {} ", marker, label)).bold(),
+            Span::styled(format!("({})", count), theme.style(Role::Accent)),
+        ]))
+        .style(theme.style(Role::GroupHeader))
+    }
+
+    // the priority, due date and tags shown after the item title
+    fn metadata_spans<'a>(item: &'a TodoItem, tag_matches: &[Vec<usize>]) -> Vec<Span<'a>> {
+        let theme = Theme::current();
+        let mut spans = Vec::new();
+
+        let priority_style = match item.priority() {
+            Priority::None => None,
+            Priority::Low => Some(theme.style(Role::PriorityLow)),
+            Priority::Medium => Some(theme.style(Role::PriorityMedium)),
+            Priority::High => Some(theme.style(Role::PriorityHigh)),
+        };
+        if let Some(style) = priority_style {
+            spans.push(Span::styled(format!(" !{}", item.priority()), style));
+        }
+        if let Some(due) = item.due() {
+            let role = if item.is_overdue() {
+                Role::Overdue
+            } else {
+                Role::Due
+            };
+            spans.push(Span::styled(
+                format!(" due {}", due.format(DATE_FORMAT)),
+                theme.style(role),
+            ));
+        }
+        if let Some(repeat) = item.repeat() {
+            spans.push(Span::styled(
+                format!(" ↻ {}", repeat),
+                theme.style(Role::Due),
+            ));
+        }
+        for (tag, matches) in item.tags().iter().zip(tag_matches) {
+            let style = theme.style(Role::Tag);
+            spans.push(Span::styled(" #", style));
+            spans.extend(highlight_matches(tag, matches, style));
+        }
+        spans
+    }
+
     fn alternate_color(n: usize) -> Style {
-        if n % 2 == 0 {
-            Style::default().on_dark_gray()
+        let theme = Theme::current();
+        if n.is_multiple_of(2) {
+            theme.style(Role::AltRow)
         } else {
-            Style::default().on_black()
+            theme.style(Role::Base)
         }
     }
 }
@@ -146,7 +1324,8 @@ impl Widget for &mut TodoList {
     where
         Self: Sized,
     {
-        if self.is_empty() {
+        self.refresh_rows();
+        if self.rows.is_empty() {
             self.render_empty_todo_list(area, buf);
         } else {
             self.render_todo_list(area, buf);
@@ -154,11 +1333,175 @@ impl Widget for &mut TodoList {
     }
 }
 
+impl VisibleItem {
+    // an item shown without matching the filter
+    fn unmatched(index: usize, item: &TodoItem) -> Self {
+        Self {
+            index,
+            depth: 0,
+            score: 0,
+            title_matches: Vec::new(),
+            tag_matches: vec![Vec::new(); item.tags.len()],
+        }
+    }
+}
+
+impl TodoItemStatus {
+    // the status new items start with
+    pub fn initial() -> Self {
+        Self::from(Workflow::current().initial())
+    }
+
+    // the status finishing an item
+    pub fn done() -> Self {
+        Self::from(Workflow::current().done())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_done(&self) -> bool {
+        Workflow::current().is_done(&self.0)
+    }
+}
+
+impl From<&str> for TodoItemStatus {
+    fn from(name: &str) -> Self {
+        Self(name.to_string())
+    }
+}
+
 impl std::fmt::Display for TodoItemStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::fmt::Display for Priority {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TodoItemStatus::Complete => write!(f, "complete"),
-            TodoItemStatus::InProgress => write!(f, "in progress"),
+            Priority::None => write!(f, "none"),
+            Priority::Low => write!(f, "low"),
+            Priority::Medium => write!(f, "medium"),
+            Priority::High => write!(f, "high"),
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "none" => Ok(Priority::None),
+            "l" | "low" => Ok(Priority::Low),
+            "m" | "med" | "medium" => Ok(Priority::Medium),
+            "h" | "high" => Ok(Priority::High),
+            other => Err(format!(
+                "unknown priority `{}`, expected none, low, medium or high",
+                other
+            )),
+        }
+    }
+}
+
+// splits the text into spans where the chars at the matched
+// indexes are highlighted on top of the base style
+fn highlight_matches<'a>(text: &'a str, matches: &[usize], style: Style) -> Vec<Span<'a>> {
+    if matches.is_empty() {
+        return vec![Span::styled(text, style)];
+    }
+
+    let highlight = style.patch(Theme::current().style(Role::Match));
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut highlighted = false;
+
+    for (i, (byte_index, _)) in text.char_indices().enumerate() {
+        let is_match = matches.contains(&i);
+        if is_match != highlighted && byte_index > start {
+            let part_style = if highlighted { highlight } else { style };
+            spans.push(Span::styled(&text[start..byte_index], part_style));
+            start = byte_index;
+        }
+        highlighted = is_match;
+    }
+    let part_style = if highlighted { highlight } else { style };
+    spans.push(Span::styled(&text[start..], part_style));
+    spans
+}
+
+// splits a comma or space separated list of tags, a leading `#` is
+// allowed and removed, empty and duplicated tags are dropped
+pub fn parse_tags(s: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in s.split(|c: char| c == ',' || c.is_whitespace()) {
+        let tag = tag.trim().trim_start_matches('#');
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list_of(titles: &[&str]) -> TodoList {
+        let mut list = TodoList::default();
+        for title in titles {
+            list.add(TodoItem::new(title.to_string(), String::new()));
         }
+        list
+    }
+
+    fn titles(list: &TodoList) -> Vec<&str> {
+        list.items()
+            .iter()
+            .map(|item| item.title().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn moving_while_searching_uses_the_manual_order_of_the_matches() {
+        let mut list = list_of(&["a piece of apple", "banana", "apple", "apple tart"]);
+        list.set_filter("apple");
+        // the best match is shown first
+        assert_eq!(
+            list.selected().map(|item| item.title().as_str()),
+            Some("apple")
+        );
+
+        list.move_current(-1).unwrap();
+        assert_eq!(
+            titles(&list),
+            ["apple", "a piece of apple", "banana", "apple tart"]
+        );
+        assert_eq!(
+            list.selected().map(|item| item.title().as_str()),
+            Some("apple")
+        );
+
+        // the rows stay in the manual order for the next moves
+        list.move_current(1).unwrap();
+        list.move_current(1).unwrap();
+        assert_eq!(
+            titles(&list),
+            ["a piece of apple", "banana", "apple tart", "apple"]
+        );
+    }
+
+    #[test]
+    fn changing_the_filter_ranks_the_matches_again() {
+        let mut list = list_of(&["a piece of apple", "apple"]);
+        list.set_filter("apple");
+        list.move_current(1).unwrap();
+        list.set_filter("apple");
+        assert_eq!(
+            list.selected().map(|item| item.title().as_str()),
+            Some("apple")
+        );
     }
 }
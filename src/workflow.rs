@@ -0,0 +1,151 @@
+use ratatui::style::{Color, Style};
+use serde::Deserialize;
+
+use crate::{
+    config,
+    theme::{Role, Theme},
+};
+
+// a single status in the workflow
+#[derive(Clone, Deserialize)]
+pub struct StatusConfig {
+    pub name: String,
+    // the color of the theme status roles is used when not set
+    #[serde(default)]
+    pub color: Option<Color>,
+    // marks the status as finishing the task, if no
+    // status is marked the last one is used
+    #[serde(default)]
+    pub done: bool,
+}
+
+// the ordered set of statuses a task moves through
+#[derive(Clone, Deserialize)]
+#[serde(try_from = "Vec<StatusConfig>")]
+pub struct Workflow {
+    statuses: Vec<StatusConfig>,
+}
+
+impl Workflow {
+    // the workflow used by the application
+    pub fn current() -> &'static Workflow {
+        &config::get().workflow
+    }
+
+    pub fn statuses(&self) -> &[StatusConfig] {
+        &self.statuses
+    }
+
+    // the status new tasks start with
+    pub fn initial(&self) -> &str {
+        &self.statuses[0].name
+    }
+
+    // the status used when a task is marked as done
+    pub fn done(&self) -> &str {
+        match self.statuses.iter().find(|s| s.done) {
+            Some(status) => &status.name,
+            None => &self.statuses[self.statuses.len() - 1].name,
+        }
+    }
+
+    pub fn is_done(&self, name: &str) -> bool {
+        name == self.done() || self.get(name).is_some_and(|s| s.done)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&StatusConfig> {
+        self.statuses.iter().find(|s| s.name == name)
+    }
+
+    // the status after `name`, wrapping around to the first one,
+    // unknown statuses move to the initial status
+    pub fn next(&self, name: &str) -> &str {
+        match self.position(name) {
+            Some(i) => &self.statuses[(i + 1) % self.statuses.len()].name,
+            None => self.initial(),
+        }
+    }
+
+    // the status before `name`, wrapping around to the last one,
+    // unknown statuses move to the initial status
+    pub fn prev(&self, name: &str) -> &str {
+        match self.position(name) {
+            Some(i) => &self.statuses[(i + self.statuses.len() - 1) % self.statuses.len()].name,
+            None => self.initial(),
+        }
+    }
+
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.statuses.iter().position(|s| s.name == name)
+    }
+
+    pub fn color(&self, name: &str) -> Option<Color> {
+        self.get(name).and_then(|status| status.color)
+    }
+
+    // the theme style of unfinished or finished statuses,
+    // with the color of the status when it has one
+    pub fn style(&self, name: &str) -> Style {
+        let role = if self.is_done(name) {
+            Role::StatusDone
+        } else {
+            Role::Status
+        };
+        let style = Theme::current().style(role);
+        match self.color(name) {
+            Some(color) => style.fg(color),
+            None => style,
+        }
+    }
+
+    // the width of the longest status name, used to align the list rows
+    pub fn name_width(&self) -> usize {
+        self.statuses
+            .iter()
+            .map(|s| s.name.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Default for Workflow {
+    fn default() -> Self {
+        Self {
+            statuses: vec![
+                StatusConfig {
+                    name: "in progress".into(),
+                    color: None,
+                    done: false,
+                },
+                StatusConfig {
+                    name: "complete".into(),
+                    color: None,
+                    done: true,
+                },
+            ],
+        }
+    }
+}
+
+impl TryFrom<Vec<StatusConfig>> for Workflow {
+    type Error = String;
+
+    fn try_from(statuses: Vec<StatusConfig>) -> Result<Self, Self::Error> {
+        if statuses.is_empty() {
+            return Err("the workflow must have at least one status".into());
+        }
+
+        for (i, status) in statuses.iter().enumerate() {
+            if status.name.trim().is_empty() {
+                return Err("status names can't be empty".into());
+            }
+            if statuses[..i].iter().any(|s| s.name == status.name) {
+                return Err(format!(
+                    "status `{}` is defined more than once",
+                    status.name
+                ));
+            }
+        }
+        Ok(Self { statuses })
+    }
+}